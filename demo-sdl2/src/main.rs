@@ -1,9 +1,12 @@
 use std::fmt::Write;
+use std::rc::Rc;
 
 use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
 use sdl2::video::GLProfile;
 
+use glow::HasContext;
+
 use microui::*;
 use microui::atlas::*;
 
@@ -11,6 +14,11 @@ use crate::renderer::Renderer;
 
 mod renderer;
 
+/// Texture of the checkerboard in the canvas window, registered with the renderer.
+const CHECKER: TextureId = TextureId(1);
+/// Id of the canvas window's region the demo fills with GL itself.
+const CANVAS_FILL: u32 = 1;
+
 const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Maecenas lacinia, sem eu lacinia molestie, mi risus faucibus ipsum, eu varius magna felis a nulla.";
 
 #[derive(Copy, Clone)]
@@ -20,7 +28,7 @@ pub struct LabelColor<'a> {
 }
 
 struct State {
-    label_colors: [LabelColor<'static>; 15],
+    label_colors: [LabelColor<'static>; 17],
     bg: [f32; 3],
     logbuf: String,
     logbuf_updated: bool,
@@ -46,6 +54,8 @@ impl State {
                 ("basefocus:", ControlColor::BaseFocus),
                 ("scrollbase:", ControlColor::ScrollBase),
                 ("scrollthumb:", ControlColor::ScrollThumb),
+                ("shadow:", ControlColor::Shadow),
+                ("focusring:", ControlColor::FocusRing),
                 ("", ControlColor::Text),
             ]
             .map(|(label, idx)| LabelColor { label, idx }),
@@ -66,7 +76,7 @@ impl State {
     }
 
     fn test_window(&mut self, ui: &mut Context) {
-        ui.window("Demo Window").position(40, 40).size(300, 450).show(ui, |ui| {
+        ui.window("Demo Window").position(40, 40).size(300, 450).options(WidgetOption::SHADOW).show(ui, |ui| {
             let mut win = ui.get_current_container_rect();
             win.w = if win.w > 240 { win.w } else { 240 };
             win.h = if win.h > 300 { win.h } else { 300 };
@@ -218,11 +228,29 @@ impl State {
                 ui.draw_rect(next_layout, ui.style.colors[i]);
                 i += 1;
             }
+            ui.layout_row(&[80, -1], 0);
+            unsafe {
+                let radius: *mut i32 = &mut ui.style.corner_radius;
+                ui.label("radius:");
+                int_slider(ui, &mut *radius, 0, 12);
+                let border: *mut i32 = &mut ui.style.border_width;
+                ui.label("border:");
+                int_slider(ui, &mut *border, 0, 4);
+            }
         })
     }
 
+    fn canvas_window(&mut self, ui: &mut Context) {
+        ui.window("Canvas").position(660, 40).size(130, 200).show(ui, |ui| {
+            ui.layout_row(&[-1], 64);
+            ui.image(CHECKER, UvRect::FULL, Color::rgba(255, 255, 255, 255));
+            ui.custom_draw(CANVAS_FILL);
+        });
+    }
+
     fn process_frame(&mut self, ui: &mut Context) {
         ui.frame(|ui| {
+            self.canvas_window(ui);
             self.style_window(ui);
             self.log_window(ui);
             self.test_window(ui);
@@ -239,6 +267,15 @@ fn uint8_slider(ui: &mut Context, value: &mut u8, low: i32, high: i32) -> Resour
     res
 }
 
+fn int_slider(ui: &mut Context, value: &mut i32, low: i32, high: i32) -> ResourceState {
+    let mut tmp = *value as f32;
+    ui.push_id_from_ptr(value);
+    let res = ui.slider_ex(&mut tmp, low as _, high as _, 1.0, 0, WidgetOption::ALIGN_CENTER);
+    *value = tmp as i32;
+    ui.pop_id();
+    res
+}
+
 fn main() {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
@@ -260,14 +297,26 @@ fn main() {
 
     let mut event_pump = sdl_context.event_pump().unwrap();
     let (width, height) = window.size();
-    let mut rd = Renderer::new(&gl, &ATLAS_TEXTURE, width, height);
+    // an atlas file written with `Atlas::write` can be passed to reskin the demo
+    let atlas = match std::env::args().nth(1) {
+        Some(path) => match std::fs::File::open(&path).map_err(AtlasError::from).and_then(|f| Atlas::read(&mut std::io::BufReader::new(f))) {
+            Ok(atlas) => Rc::new(atlas),
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                std::process::exit(1);
+            }
+        },
+        None => Rc::new(Atlas::builtin().clone()),
+    };
+    let mut rd = Renderer::new(&gl, &atlas, width, height);
+    let checker: Vec<u8> = (0..64).flat_map(|i| if (i % 8 + i / 8) % 2 == 0 { [230, 230, 230, 255] } else { [60, 60, 60, 255] }).collect();
+    rd.set_texture(&gl, CHECKER, 8, 8, &checker);
+    let mut batch = microui::batch::Batch::new();
+    batch.set_atlas(&atlas);
 
     let mut state = State::new();
 
-    pub fn r_get_char_width(_font: FontId, c: char) -> usize { ATLAS[ATLAS_FONT as usize + c as usize].w as usize }
-
-    pub fn r_get_font_height(_font: FontId) -> usize { 18 }
-    let mut ctx = microui::Context::new(r_get_char_width, r_get_font_height);
+    let mut ctx = microui::Context::new(atlas.clone());
 
     'running: loop {
         let (width, height) = window.size();
@@ -288,42 +337,115 @@ fn main() {
             }
         }
 
-        fn map_keymode(sdl_km: sdl2::keyboard::Mod, sdl_kc: Option<sdl2::keyboard::Keycode>) -> microui::KeyMode {
-            match (sdl_km, sdl_kc) {
-                (sdl2::keyboard::Mod::LALTMOD, _) | (sdl2::keyboard::Mod::RALTMOD, _) => microui::KeyMode::ALT,
-                (sdl2::keyboard::Mod::LCTRLMOD, _) | (sdl2::keyboard::Mod::RCTRLMOD, _) => microui::KeyMode::CTRL,
-                (sdl2::keyboard::Mod::LSHIFTMOD, _) | (sdl2::keyboard::Mod::RSHIFTMOD, _) => microui::KeyMode::SHIFT,
-                (_, Some(sdl2::keyboard::Keycode::Backspace)) => microui::KeyMode::BACKSPACE,
-                (_, Some(sdl2::keyboard::Keycode::Return)) => microui::KeyMode::RETURN,
-                _ => microui::KeyMode::empty(),
-            }
+        fn map_modifiers(sdl_km: sdl2::keyboard::Mod) -> microui::Modifiers {
+            use sdl2::keyboard::Mod;
+            let mut mods = microui::Modifiers::empty();
+            mods.set(microui::Modifiers::SHIFT, sdl_km.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD));
+            mods.set(microui::Modifiers::CTRL, sdl_km.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD));
+            mods.set(microui::Modifiers::ALT, sdl_km.intersects(Mod::LALTMOD | Mod::RALTMOD));
+            mods.set(microui::Modifiers::SUPER, sdl_km.intersects(Mod::LGUIMOD | Mod::RGUIMOD));
+            mods
+        }
+
+        fn map_key(sdl_kc: Keycode) -> Option<microui::Key> {
+            use microui::Key;
+            let key = match sdl_kc {
+                Keycode::Backspace => Key::Backspace,
+                Keycode::Return | Keycode::KpEnter => Key::Return,
+                Keycode::Tab => Key::Tab,
+                Keycode::Escape => Key::Escape,
+                Keycode::Space => Key::Space,
+                Keycode::Delete => Key::Delete,
+                Keycode::Insert => Key::Insert,
+                Keycode::Left => Key::Left,
+                Keycode::Right => Key::Right,
+                Keycode::Up => Key::Up,
+                Keycode::Down => Key::Down,
+                Keycode::Home => Key::Home,
+                Keycode::End => Key::End,
+                Keycode::PageUp => Key::PageUp,
+                Keycode::PageDown => Key::PageDown,
+                Keycode::F1 => Key::F1,
+                Keycode::F2 => Key::F2,
+                Keycode::F3 => Key::F3,
+                Keycode::F4 => Key::F4,
+                Keycode::F5 => Key::F5,
+                Keycode::F6 => Key::F6,
+                Keycode::F7 => Key::F7,
+                Keycode::F8 => Key::F8,
+                Keycode::F9 => Key::F9,
+                Keycode::F10 => Key::F10,
+                Keycode::F11 => Key::F11,
+                Keycode::F12 => Key::F12,
+                Keycode::A => Key::A,
+                Keycode::B => Key::B,
+                Keycode::C => Key::C,
+                Keycode::D => Key::D,
+                Keycode::E => Key::E,
+                Keycode::F => Key::F,
+                Keycode::G => Key::G,
+                Keycode::H => Key::H,
+                Keycode::I => Key::I,
+                Keycode::J => Key::J,
+                Keycode::K => Key::K,
+                Keycode::L => Key::L,
+                Keycode::M => Key::M,
+                Keycode::N => Key::N,
+                Keycode::O => Key::O,
+                Keycode::P => Key::P,
+                Keycode::Q => Key::Q,
+                Keycode::R => Key::R,
+                Keycode::S => Key::S,
+                Keycode::T => Key::T,
+                Keycode::U => Key::U,
+                Keycode::V => Key::V,
+                Keycode::W => Key::W,
+                Keycode::X => Key::X,
+                Keycode::Y => Key::Y,
+                Keycode::Z => Key::Z,
+                Keycode::Num0 => Key::Num0,
+                Keycode::Num1 => Key::Num1,
+                Keycode::Num2 => Key::Num2,
+                Keycode::Num3 => Key::Num3,
+                Keycode::Num4 => Key::Num4,
+                Keycode::Num5 => Key::Num5,
+                Keycode::Num6 => Key::Num6,
+                Keycode::Num7 => Key::Num7,
+                Keycode::Num8 => Key::Num8,
+                Keycode::Num9 => Key::Num9,
+                _ => return None,
+            };
+            Some(key)
         }
 
         for event in event_pump.poll_iter() {
+            let time = event.get_timestamp() as u64;
             match event {
                 Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => break 'running,
                 Event::Window { win_event: WindowEvent::Close, .. } => break 'running,
-                Event::MouseMotion { x, y, .. } => ctx.input_mousemove(x, y),
-                Event::MouseWheel { y, .. } => ctx.input_scroll(0, y * -30),
+                Event::MouseMotion { x, y, .. } => ctx.push_event(InputEvent::MouseMove(x, y), time),
+                Event::MouseWheel { y, .. } => ctx.push_event(InputEvent::Scroll(0, y * -30), time),
                 Event::MouseButtonDown { mouse_btn, .. } => {
                     let mb = map_mouse_button(mouse_btn);
-                    ctx.input_mousedown(mb);
+                    ctx.push_event(InputEvent::MouseDown(mb), time);
                 }
                 Event::MouseButtonUp { mouse_btn, .. } => {
                     let mb = map_mouse_button(mouse_btn);
-                    ctx.input_mouseup(mb);
+                    ctx.push_event(InputEvent::MouseUp(mb), time);
                 }
                 Event::KeyDown { keymod, keycode, .. } => {
-                    let km = map_keymode(keymod, keycode);
-                    ctx.input_keydown(km);
+                    ctx.push_event(InputEvent::Modifiers(map_modifiers(keymod)), time);
+                    if let Some(key) = keycode.and_then(map_key) {
+                        ctx.push_event(InputEvent::KeyDown(key), time);
+                    }
                 }
                 Event::KeyUp { keymod, keycode, .. } => {
-                    let km = map_keymode(keymod, keycode);
-                    ctx.input_keyup(km);
-                }
-                Event::TextInput { text, .. } => {
-                    ctx.input_text(text.as_str());
+                    ctx.push_event(InputEvent::Modifiers(map_modifiers(keymod)), time);
+                    if let Some(key) = keycode.and_then(map_key) {
+                        ctx.push_event(InputEvent::KeyUp(key), time);
+                    }
                 }
+                Event::TextInput { text, .. } => ctx.push_event(InputEvent::Text(text), time),
 
                 _ => {}
             }
@@ -331,19 +453,16 @@ fn main() {
 
         state.process_frame(&mut ctx);
 
-        for &cmd in ctx.commands() {
-            match cmd {
-                Command::Text { str_start, str_len, pos, color, .. } => {
-                    let str = &ctx.text_stack[str_start..str_start + str_len];
-                    rd.draw_text(&gl, str, pos, color)
+        batch.build(&ctx);
+        rd.render(&gl, &batch, |gl, id, _rect| {
+            if id == CANVAS_FILL {
+                // the scissor covers the region, so clearing fills it
+                unsafe {
+                    gl.clear_color(0.2, 0.5, 0.6, 1.0);
+                    gl.clear(glow::COLOR_BUFFER_BIT);
                 }
-                Command::Rect { rect, color } => rd.draw_rect(&gl, rect, color),
-                Command::Icon { id, rect, color } => rd.draw_icon(&gl, id, rect, color),
-                Command::Clip { rect } => rd.set_clip_rect(&gl, 800, 600, rect),
             }
-        }
-
-        rd.flush(&gl);
+        });
         window.gl_swap_window();
 
         ::std::thread::sleep(::std::time::Duration::new(0, 1_000_000_000u32 / 60));
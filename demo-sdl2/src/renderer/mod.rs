@@ -1,5 +1,6 @@
 use microui::*;
 use microui::atlas::*;
+use microui::batch::*;
 use glow::*;
 
 const VERTEX_SHADER: &str = "#version 100
@@ -23,25 +24,9 @@ varying lowp vec4 vVertexColor;
 uniform sampler2D uTexture;
 void main()
 {
-    lowp vec4 col = texture2D(uTexture, vTexCoord).aaaa;
-    gl_FragColor = vec4(vVertexColor.rgb, col.a * vVertexColor.a);
+    gl_FragColor = texture2D(uTexture, vTexCoord) * vVertexColor;
 }";
 
-#[derive(Default, Copy, Clone)]
-#[repr(C)]
-pub struct Vec2f {
-    x: f32,
-    y: f32,
-}
-
-#[derive(Default, Copy, Clone)]
-#[repr(C)]
-struct Vertex {
-    pos: Vec2f,
-    tex: Vec2f,
-    color: Color,
-}
-
 pub fn ortho4(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> [f32; 16] {
     let width = right - left;
     let height = top - bottom;
@@ -56,12 +41,10 @@ pub fn ortho4(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32)
 }
 
 pub struct Renderer {
-    verts: Vec<Vertex>,
-    indices: Vec<u16>,
-
     vbo: NativeBuffer,
     ibo: NativeBuffer,
     tex_o: NativeTexture,
+    textures: Vec<(TextureId, NativeTexture)>,
 
     program: NativeProgram,
 
@@ -103,8 +86,12 @@ impl Renderer {
         }
     }
 
-    pub fn new(gl: &glow::Context, atlas_texture: &[u8], width: u32, height: u32) -> Self {
+    pub fn new(gl: &glow::Context, atlas: &Atlas, width: u32, height: u32) -> Self {
         assert_eq!(core::mem::size_of::<Vertex>(), 20);
+        let pixels: Vec<u8> = match atlas.format {
+            PixelFormat::Alpha => atlas.pixels.iter().flat_map(|&a| [255, 255, 255, a]).collect(),
+            PixelFormat::Rgba => atlas.pixels.clone(),
+        };
         unsafe {
             // init texture
             let tex_o = gl.create_texture().unwrap();
@@ -114,13 +101,13 @@ impl Renderer {
             gl.tex_image_2d(
                 glow::TEXTURE_2D,
                 0,
-                glow::ALPHA as i32,
-                ATLAS_WIDTH as i32,
-                ATLAS_HEIGHT as i32,
+                glow::RGBA as i32,
+                atlas.width,
+                atlas.height,
                 0,
-                glow::ALPHA,
+                glow::RGBA,
                 glow::UNSIGNED_BYTE,
-                Some(&atlas_texture),
+                Some(&pixels),
             );
             debug_assert!(gl.get_error() == 0);
             gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::NEAREST as i32);
@@ -133,12 +120,10 @@ impl Renderer {
             let program = Self::create_program(gl, VERTEX_SHADER, FRAGMENT_SHADER);
 
             Self {
-                verts: Vec::new(),
-                indices: Vec::new(),
-
                 vbo,
                 ibo,
                 tex_o,
+                textures: Vec::new(),
                 program,
 
                 width,
@@ -147,73 +132,118 @@ impl Renderer {
         }
     }
 
-    pub fn flush(&mut self, gl: &glow::Context) {
-        if self.verts.len() == 0 || self.indices.len() == 0 {
-            return;
-        }
-
+    /// Uploads RGBA8 `pixels` for `Command::Image`s drawn with `id`, replacing any previous texture of that id.
+    pub fn set_texture(&mut self, gl: &glow::Context, id: TextureId, width: i32, height: i32, pixels: &[u8]) {
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
         unsafe {
-            // opengl rendering states
-            gl.viewport(0, 0, self.width as i32, self.height as i32);
-
-            gl.enable(glow::BLEND);
-            debug_assert!(gl.get_error() == 0);
-            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
-            debug_assert!(gl.get_error() == 0);
-            gl.disable(glow::CULL_FACE);
-            debug_assert!(gl.get_error() == 0);
-            gl.disable(glow::DEPTH_TEST);
-            debug_assert!(gl.get_error() == 0);
-            gl.enable(glow::SCISSOR_TEST);
+            if let Some(i) = self.textures.iter().position(|(t, _)| *t == id) {
+                gl.delete_texture(self.textures.swap_remove(i).1);
+            }
+            let tex = gl.create_texture().unwrap();
+            gl.bind_texture(glow::TEXTURE_2D, Some(tex));
+            gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32, width, height, 0, glow::RGBA, glow::UNSIGNED_BYTE, Some(pixels));
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::NEAREST as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::NEAREST as i32);
             debug_assert!(gl.get_error() == 0);
+            self.textures.push((id, tex));
+        }
+    }
 
-            // set the program
-            gl.use_program(Some(self.program));
-            debug_assert!(gl.get_error() == 0);
+    /// Sets the blending, program, uniforms and buffers the batch is drawn with, returning the position, texture
+    /// coordinate and color attribute locations.
+    unsafe fn bind_state(&self, gl: &glow::Context) -> [u32; 3] {
+        // opengl rendering states
+        gl.viewport(0, 0, self.width as i32, self.height as i32);
+
+        gl.enable(glow::BLEND);
+        debug_assert!(gl.get_error() == 0);
+        gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+        debug_assert!(gl.get_error() == 0);
+        gl.disable(glow::CULL_FACE);
+        debug_assert!(gl.get_error() == 0);
+        gl.disable(glow::DEPTH_TEST);
+        debug_assert!(gl.get_error() == 0);
+        gl.enable(glow::SCISSOR_TEST);
+        debug_assert!(gl.get_error() == 0);
+
+        // set the program
+        gl.use_program(Some(self.program));
+        debug_assert!(gl.get_error() == 0);
+
+        // set the texture unit
+        gl.active_texture(glow::TEXTURE0 + 0);
+        let tex_uniform_id = gl.get_uniform_location(self.program, "uTexture").unwrap();
+        gl.uniform_1_i32(Some(&tex_uniform_id), 0);
+        debug_assert_eq!(gl.get_error(), 0);
+
+        // set the viewport
+        let viewport = gl.get_uniform_location(self.program, "uTransform").unwrap();
+        let tm = ortho4(0.0, self.width as f32, self.height as f32, 0.0, -1.0, 1.0);
+        gl.uniform_matrix_4_f32_slice(Some(&viewport), false, &tm);
+        debug_assert_eq!(gl.get_error(), 0);
+
+        // set the vertex buffer
+        let pos_attrib_id = gl.get_attrib_location(self.program, "vertexPosition").unwrap();
+        let tex_attrib_id = gl.get_attrib_location(self.program, "vertexTexCoord").unwrap();
+        let col_attrib_id = gl.get_attrib_location(self.program, "vertexColor").unwrap();
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.ibo));
+        gl.enable_vertex_attrib_array(pos_attrib_id);
+        gl.enable_vertex_attrib_array(tex_attrib_id);
+        gl.enable_vertex_attrib_array(col_attrib_id);
+        debug_assert!(gl.get_error() == 0);
+        [pos_attrib_id, tex_attrib_id, col_attrib_id]
+    }
 
-            // set the texture
-            gl.bind_texture(glow::TEXTURE_2D, Some(self.tex_o));
-            gl.active_texture(glow::TEXTURE0 + 0);
-            let tex_uniform_id = gl.get_uniform_location(self.program, "uTexture").unwrap();
-            gl.uniform_1_i32(Some(&tex_uniform_id), 0);
-            debug_assert_eq!(gl.get_error(), 0);
-
-            // set the viewport
-            let viewport = gl.get_uniform_location(self.program, "uTransform").unwrap();
-            let tm = ortho4(0.0, self.width as f32, self.height as f32, 0.0, -1.0, 1.0);
-            gl.uniform_matrix_4_f32_slice(Some(&viewport), false, &tm);
-            debug_assert_eq!(gl.get_error(), 0);
-
-            // set the vertex buffer
-            let pos_attrib_id = gl.get_attrib_location(self.program, "vertexPosition").unwrap();
-            let tex_attrib_id = gl.get_attrib_location(self.program, "vertexTexCoord").unwrap();
-            let col_attrib_id = gl.get_attrib_location(self.program, "vertexColor").unwrap();
-            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
-            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.ibo));
-            debug_assert!(gl.get_error() == 0);
+    /// Draws `batch`, binding the atlas or the texture set with `set_texture` for each draw call. Calls with an
+    /// unknown texture are skipped. `custom` is called for every `Command::Custom` region with its id and rect, with
+    /// the scissor set to the region's visible part; the renderer's state is restored afterwards.
+    pub fn render(&mut self, gl: &glow::Context, batch: &Batch, mut custom: impl FnMut(&glow::Context, u32, Rect)) {
+        if batch.draw_calls.is_empty() {
+            return;
+        }
+
+        unsafe {
+            let [pos_attrib_id, tex_attrib_id, col_attrib_id] = self.bind_state(gl);
 
             // update the vertex buffer
-            let vertices_u8: &[u8] = core::slice::from_raw_parts(self.verts.as_ptr() as *const u8, self.verts.len() * core::mem::size_of::<Vertex>());
+            let vertices_u8: &[u8] = core::slice::from_raw_parts(batch.vertices.as_ptr() as *const u8, batch.vertices.len() * core::mem::size_of::<Vertex>());
             gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, vertices_u8, glow::DYNAMIC_DRAW);
             debug_assert!(gl.get_error() == 0);
 
             // update the index buffer
-            let indices_u8: &[u8] = core::slice::from_raw_parts(self.indices.as_ptr() as *const u8, self.indices.len() * core::mem::size_of::<u16>());
+            let indices_u8: &[u8] = core::slice::from_raw_parts(batch.indices.as_ptr() as *const u8, batch.indices.len() * core::mem::size_of::<u16>());
             gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, indices_u8, glow::DYNAMIC_DRAW);
             debug_assert!(gl.get_error() == 0);
 
-            gl.enable_vertex_attrib_array(pos_attrib_id);
-            gl.enable_vertex_attrib_array(tex_attrib_id);
-            gl.enable_vertex_attrib_array(col_attrib_id);
-            debug_assert!(gl.get_error() == 0);
-
-            gl.vertex_attrib_pointer_f32(pos_attrib_id, 2, glow::FLOAT, false, 20, 0);
-            gl.vertex_attrib_pointer_f32(tex_attrib_id, 2, glow::FLOAT, false, 20, 8);
-            gl.vertex_attrib_pointer_f32(col_attrib_id, 4, glow::UNSIGNED_BYTE, true, 20, 16);
-            debug_assert!(gl.get_error() == 0);
-
-            gl.draw_elements(glow::TRIANGLES, self.indices.len() as i32, glow::UNSIGNED_SHORT, 0);
-            debug_assert!(gl.get_error() == 0);
+            for call in &batch.draw_calls {
+                if let Some((id, rect)) = call.custom {
+                    let r = rect.intersect(call.clip);
+                    gl.scissor(r.x, self.height as i32 - (r.y + r.h), r.w, r.h);
+                    custom(gl, id, rect);
+                    self.bind_state(gl);
+                    continue;
+                }
+                let tex = match call.texture {
+                    None => self.tex_o,
+                    Some(id) => match self.textures.iter().find(|(t, _)| *t == id) {
+                        Some(&(_, tex)) => tex,
+                        None => continue,
+                    },
+                };
+                gl.bind_texture(glow::TEXTURE_2D, Some(tex));
+
+                let base = (call.vertex_offset * core::mem::size_of::<Vertex>()) as i32;
+                gl.vertex_attrib_pointer_f32(pos_attrib_id, 2, glow::FLOAT, false, 20, base);
+                gl.vertex_attrib_pointer_f32(tex_attrib_id, 2, glow::FLOAT, false, 20, base + 8);
+                gl.vertex_attrib_pointer_f32(col_attrib_id, 4, glow::UNSIGNED_BYTE, true, 20, base + 16);
+                debug_assert!(gl.get_error() == 0);
+
+                let clip = call.clip;
+                gl.scissor(clip.x, self.height as i32 - (clip.y + clip.h), clip.w, clip.h);
+                gl.draw_elements(glow::TRIANGLES, call.index_count as i32, glow::UNSIGNED_SHORT, (call.index_offset * 2) as i32);
+                debug_assert!(gl.get_error() == 0);
+            }
 
             gl.disable_vertex_attrib_array(pos_attrib_id);
             gl.disable_vertex_attrib_array(tex_attrib_id);
@@ -221,100 +251,6 @@ impl Renderer {
             debug_assert!(gl.get_error() == 0);
             gl.use_program(None);
             debug_assert!(gl.get_error() == 0);
-
-            self.verts.clear();
-            self.indices.clear();
-        }
-    }
-
-    fn push_quad_vertices(&mut self, gl: &glow::Context, v0: &Vertex, v1: &Vertex, v2: &Vertex, v3: &Vertex) {
-        if self.verts.len() + 4 >= 65536 || self.indices.len() + 6 >= 65536 {
-            self.flush(gl);
-        }
-
-        let is = self.verts.len() as u16;
-        self.indices.push(is + 0);
-        self.indices.push(is + 1);
-        self.indices.push(is + 2);
-        self.indices.push(is + 2);
-        self.indices.push(is + 3);
-        self.indices.push(is + 0);
-
-        self.verts.push(v0.clone());
-        self.verts.push(v1.clone());
-        self.verts.push(v2.clone());
-        self.verts.push(v3.clone());
-    }
-
-    pub fn push_rect(&mut self, gl: &glow::Context, dst: Rect, src: Rect, color: Color) {
-        let x = src.x as f32 / ATLAS_WIDTH as f32;
-        let y = src.y as f32 / ATLAS_HEIGHT as f32;
-        let w = src.w as f32 / ATLAS_WIDTH as f32;
-        let h = src.h as f32 / ATLAS_HEIGHT as f32;
-
-        let mut v0 = Vertex::default();
-        let mut v1 = Vertex::default();
-        let mut v2 = Vertex::default();
-        let mut v3 = Vertex::default();
-
-        // tex coordinates
-        v0.tex.x = x;
-        v0.tex.y = y;
-        v1.tex.x = x + w;
-        v1.tex.y = y;
-        v2.tex.x = x + w;
-        v2.tex.y = y + h;
-        v3.tex.x = x;
-        v3.tex.y = y + h;
-
-        // position
-        v0.pos.x = dst.x as f32;
-        v0.pos.y = dst.y as f32;
-        v1.pos.x = dst.x as f32 + dst.w as f32;
-        v1.pos.y = dst.y as f32;
-        v2.pos.x = dst.x as f32 + dst.w as f32;
-        v2.pos.y = dst.y as f32 + dst.h as f32;
-        v3.pos.x = dst.x as f32;
-        v3.pos.y = dst.y as f32 + dst.h as f32;
-
-        // color
-        v0.color = Color::rgba(color.r, color.g, color.b, color.a);
-        v1.color = v0.color;
-        v2.color = v0.color;
-        v3.color = v0.color;
-
-        self.push_quad_vertices(gl, &v0, &v1, &v2, &v3);
-    }
-
-    pub fn draw_rect(&mut self, gl: &glow::Context, rect: Rect, color: Color) { self.push_rect(gl, rect, ATLAS[ATLAS_WHITE as usize], color); }
-
-    pub fn draw_text(&mut self, gl: &glow::Context, text: &str, pos: Vec2, color: Color) {
-        let mut dst = Rect { x: pos.x, y: pos.y, w: 0, h: 0 };
-        for p in text.chars() {
-            if (p as usize) < 127 {
-                let chr = usize::min(p as usize, 127);
-                let src = ATLAS[ATLAS_FONT as usize + chr];
-                dst.w = src.w;
-                dst.h = src.h;
-                self.push_rect(gl, dst, src, color);
-                dst.x += dst.w;
-            }
-        }
-    }
-
-    pub fn draw_icon(&mut self, gl: &glow::Context, id: Icon, r: Rect, color: Color) {
-        let src = ATLAS[id as usize];
-        let x = r.x + (r.w - src.w) / 2;
-        let y = r.y + (r.h - src.h) / 2;
-        self.push_rect(gl, rect(x, y, src.w, src.h), src, color);
-    }
-
-    pub fn set_clip_rect(&mut self, gl: &glow::Context, width: i32, height: i32, rect: Rect) {
-        unsafe {
-            self.width = width as u32;
-            self.height = height as u32;
-            self.flush(gl);
-            gl.scissor(rect.x, height - (rect.y + rect.h), rect.w, rect.h);
         }
     }
 
@@ -322,7 +258,7 @@ impl Renderer {
         unsafe {
             self.width = width as u32;
             self.height = height as u32;
-            self.flush(gl);
+            gl.disable(glow::SCISSOR_TEST);
             gl.clear_color(clr.r as f32 / 255.0, clr.g as f32 / 255.0, clr.b as f32 / 255.0, clr.a as f32 / 255.0);
             gl.clear(glow::COLOR_BUFFER_BIT);
         }
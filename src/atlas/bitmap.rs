@@ -0,0 +1,239 @@
+use std::fmt;
+use std::str::SplitWhitespace;
+
+use crate::atlas::*;
+
+#[derive(Debug)]
+pub enum FontError {
+    /// Malformed BDF, with the 1-based number of the offending line.
+    Bdf(usize),
+    BadPsfMagic,
+    TruncatedPsf,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bdf(line) => write!(f, "malformed bdf font at line {}", line),
+            Self::BadPsfMagic => write!(f, "not a psf font"),
+            Self::TruncatedPsf => write!(f, "truncated psf font"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// A character's coverage bitmap as loaded from a font file.
+#[derive(Clone)]
+pub struct BitmapGlyph {
+    pub ch: char,
+    pub width: i32,
+    pub height: i32,
+    /// Offset of the bitmap's top left from the pen position at the top of the line.
+    pub offset: Vec2,
+    pub advance: i32,
+    /// `width * height` bytes of alpha coverage.
+    pub alpha: Vec<u8>,
+}
+
+/// A bitmap font to be packed into an `Atlas` with `AtlasBuilder`.
+#[derive(Clone, Default)]
+pub struct BitmapFont {
+    pub ascent: i32,
+    pub descent: i32,
+    pub glyphs: Vec<BitmapGlyph>,
+}
+
+impl BitmapFont {
+    /// The ASCII font of `Atlas::builtin`.
+    pub fn builtin() -> Self {
+        let atlas = Atlas::builtin();
+        let face = &atlas.fonts[0];
+        let mut glyphs: Vec<_> = face
+            .glyphs
+            .iter()
+            .map(|(&ch, g)| BitmapGlyph {
+                ch,
+                width: g.rect.w,
+                height: g.rect.h,
+                offset: g.offset,
+                advance: g.advance,
+                alpha: crop(&atlas.pixels, atlas.width, g.rect),
+            })
+            .collect();
+        glyphs.sort_by_key(|g| g.ch);
+        Self { ascent: face.ascent, descent: face.descent, glyphs }
+    }
+
+    /// Parses a font in the Glyph Bitmap Distribution Format. Glyphs without a Unicode encoding are skipped.
+    pub fn from_bdf(src: &str) -> Result<Self, FontError> {
+        let mut font = Self::default();
+        let (mut ascent, mut descent, mut bbox) = (None, None, None);
+        let (mut encoding, mut dwidth, mut bbx) = (None, None, None);
+        let mut lines = src.lines().enumerate();
+        let mut started = false;
+        while let Some((n, line)) = lines.next() {
+            let err = FontError::Bdf(n + 1);
+            let mut words = line.split_whitespace();
+            let Some(key) = words.next() else {
+                continue;
+            };
+            if !started {
+                if key != "STARTFONT" {
+                    return Err(err);
+                }
+                started = true;
+            }
+            match key {
+                "FONTBOUNDINGBOX" => bbox = Some(ints::<4>(words).ok_or(err)?),
+                "FONT_ASCENT" => ascent = Some(ints::<1>(words).ok_or(err)?[0]),
+                "FONT_DESCENT" => descent = Some(ints::<1>(words).ok_or(err)?[0]),
+                "STARTCHAR" => (encoding, dwidth, bbx) = (None, None, None),
+                "ENCODING" => encoding = Some(ints::<1>(words).ok_or(err)?[0]),
+                "DWIDTH" => dwidth = Some(ints::<2>(words).ok_or(err)?[0]),
+                "BBX" => bbx = Some(ints::<4>(words).ok_or(err)?),
+                "BITMAP" => {
+                    let [w, h, x, y] = bbx.or(bbox).ok_or(err)?;
+                    // grown as rows are read, so a bogus size fails on missing data instead of allocating for it
+                    let mut alpha = Vec::new();
+                    for _ in 0..h {
+                        let (n, line) = lines.next().ok_or(FontError::Bdf(n + 2))?;
+                        let hex = line.trim().as_bytes();
+                        for col in 0..w {
+                            let nibble = hex.get(col as usize / 4).and_then(|&d| (d as char).to_digit(16)).ok_or(FontError::Bdf(n + 1))?;
+                            alpha.push(if nibble & (8 >> (col % 4)) != 0 { 255 } else { 0 });
+                        }
+                    }
+                    let ch = encoding.filter(|&e| e >= 0).and_then(|e| char::from_u32(e as u32));
+                    if let Some(ch) = ch {
+                        font.glyphs.push(BitmapGlyph {
+                            ch,
+                            width: w,
+                            height: h,
+                            // relative to the baseline until the ascent is known
+                            offset: vec2(x, -(y + h)),
+                            advance: dwidth.unwrap_or(w),
+                            alpha,
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let bbox = bbox.unwrap_or_default();
+        font.ascent = ascent.unwrap_or(bbox[1] + bbox[3]);
+        font.descent = descent.unwrap_or(-bbox[3]);
+        for glyph in &mut font.glyphs {
+            glyph.offset.y += font.ascent;
+        }
+        Ok(font)
+    }
+
+    /// Parses a PC Screen Font, version 1 or 2. Without a Unicode table glyph `i` is taken to be `char` `i`.
+    pub fn from_psf(data: &[u8]) -> Result<Self, FontError> {
+        let (count, width, height, charsize, start, table) = if data.starts_with(&[0x36, 0x04]) {
+            let (mode, height) = (*data.get(2).ok_or(FontError::TruncatedPsf)?, *data.get(3).ok_or(FontError::TruncatedPsf)? as usize);
+            let count = if mode & 0x01 != 0 { 512 } else { 256 };
+            (count, 8, height, height, 4, (mode & 0x06 != 0).then_some(Table::Psf1))
+        } else if data.starts_with(&[0x72, 0xb5, 0x4a, 0x86]) {
+            let field = |i: usize| data.get(i * 4..i * 4 + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize).ok_or(FontError::TruncatedPsf);
+            let (start, flags, count, charsize, height, width) = (field(2)?, field(3)?, field(4)?, field(5)?, field(6)?, field(7)?);
+            (count, width, height, charsize, start, (flags & 0x01 != 0).then_some(Table::Psf2))
+        } else {
+            return Err(FontError::BadPsfMagic);
+        };
+
+        // every size comes from the header, so check them against each other and the data before allocating
+        let row_bytes = width.div_ceil(8);
+        if charsize == 0 || row_bytes.checked_mul(height).is_none_or(|n| charsize < n) {
+            return Err(FontError::TruncatedPsf);
+        }
+        let end = count.checked_mul(charsize).and_then(|n| n.checked_add(start)).ok_or(FontError::TruncatedPsf)?;
+        let bitmaps = data.get(start..end).ok_or(FontError::TruncatedPsf)?;
+        let chars = match table {
+            Some(table) => table.parse(&data[end..], count),
+            None => (0..count).map(|i| char::from_u32(i as u32).into_iter().collect()).collect(),
+        };
+
+        let mut font = Self {
+            ascent: height as i32,
+            descent: 0,
+            glyphs: Vec::new(),
+        };
+        for (bitmap, chars) in bitmaps.chunks_exact(charsize).zip(chars) {
+            let mut alpha = vec![0; width * height];
+            for y in 0..height {
+                for x in 0..width {
+                    if bitmap.get(y * row_bytes + x / 8).is_some_and(|b| b & (0x80 >> (x % 8)) != 0) {
+                        alpha[y * width + x] = 255;
+                    }
+                }
+            }
+            font.glyphs.extend(chars.into_iter().map(|ch| BitmapGlyph {
+                ch,
+                width: width as i32,
+                height: height as i32,
+                offset: vec2(0, 0),
+                advance: width as i32,
+                alpha: alpha.clone(),
+            }));
+        }
+        Ok(font)
+    }
+
+    /// Keeps only the glyphs of the characters `f` returns true for, e.g. to limit a large font to Latin-1.
+    pub fn retain(&mut self, mut f: impl FnMut(char) -> bool) { self.glyphs.retain(|g| f(g.ch)) }
+}
+
+#[derive(Copy, Clone)]
+enum Table {
+    Psf1,
+    Psf2,
+}
+
+impl Table {
+    /// Reads the characters mapped to each of the `count` glyphs, ignoring multi-character sequences.
+    fn parse(self, data: &[u8], count: usize) -> Vec<Vec<char>> {
+        let mut res = vec![Vec::new(); count];
+        match self {
+            Self::Psf1 => {
+                let mut units = data.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+                for chars in &mut res {
+                    let mut seq = false;
+                    for unit in units.by_ref().take_while(|&u| u != 0xffff) {
+                        seq |= unit == 0xfffe;
+                        if !seq {
+                            chars.extend(char::from_u32(unit as u32));
+                        }
+                    }
+                }
+            }
+            Self::Psf2 => {
+                let mut entries = data.split(|&b| b == 0xff);
+                for chars in &mut res {
+                    let entry = entries.next().unwrap_or_default();
+                    let single = entry.split(|&b| b == 0xfe).next().unwrap_or_default();
+                    chars.extend(String::from_utf8_lossy(single).chars().filter(|&c| c != char::REPLACEMENT_CHARACTER));
+                }
+            }
+        }
+        res
+    }
+}
+
+fn ints<const N: usize>(mut words: SplitWhitespace) -> Option<[i32; N]> {
+    let mut res = [0; N];
+    for v in &mut res {
+        *v = words.next()?.parse().ok()?;
+    }
+    Some(res)
+}
+
+/// Copies the `r` region out of an alpha image `stride` pixels wide.
+pub(crate) fn crop(pixels: &[u8], stride: i32, r: Rect) -> Vec<u8> {
+    (r.y..r.y + r.h)
+        .flat_map(|y| &pixels[(y * stride + r.x) as usize..(y * stride + r.x + r.w) as usize])
+        .copied()
+        .collect()
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::atlas::*;
+
+/// Gap left right of and below every rect packed into an `Atlas`, so filtering does not bleed between them.
+const PAD: i32 = 1;
+
+/// Where a character's bitmap sits in the atlas and how it is placed relative to the pen.
+#[derive(Copy, Clone)]
+pub struct Glyph {
+    pub rect: Rect,
+    /// Offset of the bitmap's top left from the pen position at the top of the line.
+    pub offset: Vec2,
+    pub advance: i32,
+}
+
+/// The glyph table and metrics of one `FontId` in an `Atlas`.
+#[derive(Clone, Default)]
+pub struct FontFace {
+    pub ascent: i32,
+    pub descent: i32,
+    pub glyphs: HashMap<char, Glyph>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum PixelFormat {
+    /// One byte of coverage per pixel.
+    Alpha,
+    /// Four bytes per pixel, for atlases holding colored images.
+    Rgba,
+}
+
+/// Bitmap data copied into an `Atlas` with `Atlas::insert`, row by row.
+#[derive(Copy, Clone)]
+pub enum Pixels<'a> {
+    Alpha(&'a [u8]),
+    Rgba(&'a [u8]),
+}
+
+/// A texture with the rect table backends draw rects, text and icons from.
+#[derive(Clone)]
+pub struct Atlas {
+    pub width: i32,
+    pub height: i32,
+    /// `width * height` pixels in `format`.
+    pub pixels: Vec<u8>,
+    /// `Alpha` until an RGBA bitmap is inserted, from then on coverage is stored as white pixels.
+    pub format: PixelFormat,
+    /// A fully opaque patch sampled for untextured geometry.
+    pub white: Rect,
+    /// Icon rects indexed by `Icon::index`.
+    pub icons: Vec<Rect>,
+    /// Glyph tables indexed by `FontId`.
+    pub fonts: Vec<FontFace>,
+    packer: Packer,
+}
+
+impl Atlas {
+    /// An alpha atlas holding only the white patch. The height doubles whenever an insertion needs more room.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is less than 4, too narrow for the padded white patch, or `height` is negative.
+    pub fn new(width: i32, height: i32) -> Self {
+        assert!(width >= 3 + PAD, "atlas must be at least 4 pixels wide");
+        assert!(height >= 0, "atlas height is negative");
+        let mut res = Self {
+            width,
+            height,
+            pixels: vec![0; (width * height) as usize],
+            format: PixelFormat::Alpha,
+            white: Rect::ZERO,
+            icons: vec![Rect::ZERO],
+            fonts: Vec::new(),
+            packer: Packer::new(width, height),
+        };
+        res.white = res.insert(3, 3, Pixels::Alpha(&[255; 9]));
+        res
+    }
+
+    /// The compile-time `ATLAS_TEXTURE` with the `ATLAS` and `ATLAS_ICONS` tables, its font as `FontId(0)`.
+    pub fn builtin() -> &'static Atlas {
+        static BUILTIN: OnceLock<Atlas> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let glyphs = (0..127u8)
+                .map(|c| {
+                    let rect = ATLAS[ATLAS_FONT as usize + c as usize];
+                    (c as char, Glyph { rect, offset: vec2(0, 0), advance: rect.w })
+                })
+                .collect();
+            Atlas::from_parts(
+                ATLAS_WIDTH as i32,
+                ATLAS_HEIGHT as i32,
+                ATLAS_TEXTURE.to_vec(),
+                PixelFormat::Alpha,
+                ATLAS[ATLAS_WHITE as usize],
+                ATLAS_ICONS.to_vec(),
+                vec![FontFace { ascent: 18, descent: 0, glyphs }],
+            )
+        })
+    }
+
+    /// An atlas over existing pixels, with the white patch, icons and glyphs marked as taken for later insertions.
+    pub(super) fn from_parts(width: i32, height: i32, pixels: Vec<u8>, format: PixelFormat, white: Rect, icons: Vec<Rect>, fonts: Vec<FontFace>) -> Self {
+        let glyphs = fonts.iter().flat_map(|f| f.glyphs.values().map(|g| g.rect));
+        let used = std::iter::once(white).chain(icons.iter().copied()).chain(glyphs).filter(|r| r.w > 0 && r.h > 0);
+        let packer = Packer::with_used(width, height, used.map(|r| rect(r.x, r.y, r.w + PAD, r.h + PAD)));
+        Self {
+            width,
+            height,
+            pixels,
+            format,
+            white,
+            icons,
+            fonts,
+            packer,
+        }
+    }
+
+    /// The face of `font`, falling back to the first one for ids the atlas has no font for.
+    pub fn face(&self, font: FontId) -> Option<&FontFace> { self.fonts.get(font.0).or(self.fonts.first()) }
+
+    pub fn glyph(&self, font: FontId, c: char) -> Option<&Glyph> { self.face(font)?.glyphs.get(&c) }
+
+    pub fn icon(&self, id: Icon) -> Rect { self.icons.get(id.index()).copied().unwrap_or(Rect::ZERO) }
+
+    /// Lays out `text` from `pos` as `(dst, src)` pairs: where each glyph goes on screen at `scale` and its rect in the
+    /// atlas. Pairs of characters are kerned by `kerning`, which should be the font the `Context` measures text with.
+    pub fn layout_text<'s>(&'s self, kerning: &'s dyn Font, font: FontId, text: &'s str, pos: Vec2, scale: f32) -> impl Iterator<Item = (Rect, Rect)> + 's {
+        let (mut advance, mut prev) = (0, None);
+        text.chars().filter_map(move |c| {
+            if let Some(prev) = prev {
+                advance += kerning.kerning(font, prev, c);
+            }
+            prev = Some(c);
+            let g = self.glyph(font, c)?;
+            let dst = rect(advance + g.offset.x, g.offset.y, g.rect.w, g.rect.h).scale(scale);
+            advance += g.advance;
+            Some((rect(pos.x + dst.x, pos.y + dst.y, dst.w, dst.h), g.rect))
+        })
+    }
+
+    /// Where icon `id` goes when centered in `r` at `scale`, with its rect in the atlas.
+    pub fn layout_icon(&self, id: Icon, r: Rect, scale: f32) -> (Rect, Rect) {
+        let src = self.icon(id);
+        let (w, h) = (scale_len(src.w, scale), scale_len(src.h, scale));
+        (rect(r.x + (r.w - w) / 2, r.y + (r.h - h) / 2, w, h), src)
+    }
+
+    /// The pixel at `x`, `y`, with coverage-only pixels read as white.
+    pub fn texel(&self, x: i32, y: i32) -> Color {
+        let i = (y * self.width + x) as usize;
+        match self.format {
+            PixelFormat::Alpha => Color::rgba(255, 255, 255, self.pixels[i]),
+            PixelFormat::Rgba => Color::from([self.pixels[i * 4], self.pixels[i * 4 + 1], self.pixels[i * 4 + 2], self.pixels[i * 4 + 3]]),
+        }
+    }
+
+    /// Copies a `width` by `height` bitmap into free space and returns where it went. Rects handed out earlier,
+    /// including `white` and the glyphs, stay valid.
+    ///
+    /// # Panics
+    ///
+    /// If the bitmap is wider than the atlas less one pixel of padding, or `pixels` holds fewer than
+    /// `width * height` pixels.
+    pub fn insert(&mut self, width: i32, height: i32, pixels: Pixels) -> Rect {
+        if width <= 0 || height <= 0 {
+            return Rect::ZERO;
+        }
+        assert!(width + PAD <= self.width, "bitmap is wider than the atlas");
+        let len = match pixels {
+            Pixels::Alpha(src) => src.len(),
+            Pixels::Rgba(src) => src.len() / 4,
+        };
+        assert!(len >= width as usize * height as usize, "bitmap has fewer than width * height pixels");
+        let r = loop {
+            match self.packer.pack(width + PAD, height + PAD) {
+                Some(r) => break rect(r.x, r.y, width, height),
+                None => self.grow(),
+            }
+        };
+
+        if let (Pixels::Rgba(_), PixelFormat::Alpha) = (pixels, self.format) {
+            self.pixels = self.pixels.iter().flat_map(|&a| [255, 255, 255, a]).collect();
+            self.format = PixelFormat::Rgba;
+        }
+        let (w, bpp) = (width as usize, self.bytes_per_pixel());
+        for y in 0..height as usize {
+            let dst = &mut self.pixels[((r.y as usize + y) * self.width as usize + r.x as usize) * bpp..][..w * bpp];
+            match pixels {
+                Pixels::Alpha(src) if bpp == 1 => dst.copy_from_slice(&src[y * w..][..w]),
+                Pixels::Alpha(src) => {
+                    for (px, &a) in dst.chunks_exact_mut(4).zip(&src[y * w..][..w]) {
+                        px.copy_from_slice(&[255, 255, 255, a]);
+                    }
+                }
+                Pixels::Rgba(src) => dst.copy_from_slice(&src[y * w * 4..][..w * 4]),
+            }
+        }
+        r
+    }
+
+    /// Inserts an application icon and registers it under the next `Icon::User`. Panics like `insert`.
+    pub fn add_icon(&mut self, width: i32, height: i32, pixels: Pixels) -> Icon {
+        let r = self.insert(width, height, pixels);
+        self.icons.resize(self.icons.len().max(Icon::BUILTIN.len() + 1), Rect::ZERO);
+        self.icons.push(r);
+        Icon::from_index(self.icons.len() - 1).unwrap()
+    }
+
+    /// Inserts `glyph` into the glyph table of `font`, replacing any previous glyph for its character. Panics like
+    /// `insert`.
+    pub fn add_glyph(&mut self, font: FontId, glyph: &BitmapGlyph) {
+        let rect = self.insert(glyph.width, glyph.height, Pixels::Alpha(&glyph.alpha));
+        if self.fonts.len() <= font.0 {
+            self.fonts.resize_with(font.0 + 1, FontFace::default);
+        }
+        let (ch, glyph) = (glyph.ch, Glyph { rect, offset: glyph.offset, advance: glyph.advance });
+        self.fonts[font.0].glyphs.insert(ch, glyph);
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        match self.format {
+            PixelFormat::Alpha => 1,
+            PixelFormat::Rgba => 4,
+        }
+    }
+
+    fn grow(&mut self) {
+        self.height = (self.height * 2).max(1);
+        self.pixels.resize(self.width as usize * self.height as usize * self.bytes_per_pixel(), 0);
+        self.packer.grow(self.height);
+    }
+}
+
+impl Font for Atlas {
+    fn char_width(&self, font: FontId, c: char) -> i32 { self.glyph(font, c).map_or(0, |g| g.advance) }
+
+    fn font_height(&self, font: FontId) -> i32 { self.face(font).map_or(0, |f| f.ascent + f.descent) }
+
+    fn descent(&self, font: FontId) -> i32 { self.face(font).map_or(0, |f| f.descent) }
+}
+
+/// Packs bitmap fonts together with the built-in and application icons into a new `Atlas`.
+pub struct AtlasBuilder {
+    width: i32,
+    icons: Vec<(i32, i32, Vec<u8>)>,
+    fonts: Vec<BitmapFont>,
+}
+
+impl Default for AtlasBuilder {
+    fn default() -> Self { Self::new() }
+}
+
+impl AtlasBuilder {
+    pub const fn new() -> Self {
+        Self {
+            width: 256,
+            icons: Vec::new(),
+            fonts: Vec::new(),
+        }
+    }
+
+    /// Sets the texture width, 256 by default. The height grows to fit the glyphs.
+    pub const fn width(mut self, width: i32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Adds an icon of `width * height` bytes of alpha coverage under the next `Icon::User`, counting from `Icon::User(0)`.
+    pub fn icon(mut self, width: i32, height: i32, alpha: Vec<u8>) -> Self {
+        assert_eq!(alpha.len(), (width * height) as usize);
+        self.icons.push((width, height, alpha));
+        self
+    }
+
+    /// Adds `font` under the next `FontId`, counting from `FontId(0)` in the order fonts are added.
+    pub fn font(mut self, font: BitmapFont) -> Self {
+        self.fonts.push(font);
+        self
+    }
+
+    pub fn build(&self) -> Atlas {
+        // the built-in and application icons, then every glyph of every font
+        let mut bitmaps: Vec<(i32, i32, Vec<u8>)> = ATLAS_ICONS[1..].iter().map(|&r| (r.w, r.h, crop(&ATLAS_TEXTURE, ATLAS_WIDTH as i32, r))).collect();
+        bitmaps.extend(self.icons.iter().cloned());
+        for font in &self.fonts {
+            bitmaps.extend(font.glyphs.iter().map(|g| (g.width, g.height, g.alpha.clone())));
+        }
+
+        // tallest first packs tighter
+        let width = bitmaps.iter().map(|b| b.0 + PAD).fold(self.width, i32::max);
+        let mut atlas = Atlas::new(width, 32);
+        let mut order: Vec<usize> = (0..bitmaps.len()).collect();
+        order.sort_by_key(|&i| -bitmaps[i].1);
+        let mut rects = vec![Rect::ZERO; bitmaps.len()];
+        for i in order {
+            let (w, h, alpha) = &bitmaps[i];
+            rects[i] = atlas.insert(*w, *h, Pixels::Alpha(alpha));
+        }
+
+        let mut rects = rects.into_iter();
+        atlas.icons.extend(rects.by_ref().take(Icon::BUILTIN.len() + self.icons.len()));
+        atlas.fonts = self
+            .fonts
+            .iter()
+            .map(|font| FontFace {
+                ascent: font.ascent,
+                descent: font.descent,
+                glyphs: font
+                    .glyphs
+                    .iter()
+                    .zip(rects.by_ref())
+                    .map(|(g, rect)| (g.ch, Glyph { rect, offset: g.offset, advance: g.advance }))
+                    .collect(),
+            })
+            .collect();
+        atlas
+    }
+}
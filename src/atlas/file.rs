@@ -0,0 +1,145 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::atlas::*;
+use crate::wire::{read_i32, read_point, read_rect, read_u32, read_u8, write_i32, write_point, write_rect, write_u32};
+
+pub const ATLAS_MAGIC: [u8; 4] = *b"MUIA";
+pub const ATLAS_VERSION: u16 = 1;
+/// Largest width or height `Atlas::read` accepts.
+pub const ATLAS_MAX_SIZE: i32 = 16384;
+
+#[derive(Debug)]
+pub enum AtlasError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    InvalidFormat(u8),
+    InvalidChar(u32),
+    /// A width or height that is not positive or exceeds `ATLAS_MAX_SIZE`.
+    InvalidSize(i32, i32),
+    /// A white patch, icon or glyph rect not inside the texture.
+    RectOutOfBounds(Rect),
+}
+
+impl fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::BadMagic => write!(f, "not a microui atlas"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported atlas version {}", v),
+            Self::InvalidFormat(format) => write!(f, "invalid pixel format {}", format),
+            Self::InvalidChar(c) => write!(f, "invalid glyph character {:#x}", c),
+            Self::InvalidSize(w, h) => write!(f, "invalid atlas size {}x{}", w, h),
+            Self::RectOutOfBounds(r) => write!(f, "rect {},{} {}x{} is outside the atlas", r.x, r.y, r.w, r.h),
+        }
+    }
+}
+
+impl std::error::Error for AtlasError {}
+
+impl From<io::Error> for AtlasError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl Atlas {
+    /// Writes the texture together with the white patch, icon table and every font's metrics and glyphs.
+    ///
+    /// Rects returned by `insert` that are neither icons nor glyphs are not recorded, so a loaded atlas may pack
+    /// new bitmaps over them.
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&ATLAS_MAGIC)?;
+        w.write_all(&ATLAS_VERSION.to_le_bytes())?;
+        write_i32(w, self.width)?;
+        write_i32(w, self.height)?;
+        w.write_all(&[self.format as u8])?;
+        w.write_all(&self.pixels)?;
+
+        write_rect(w, self.white)?;
+        write_u32(w, self.icons.len() as u32)?;
+        for &icon in &self.icons {
+            write_rect(w, icon)?;
+        }
+
+        write_u32(w, self.fonts.len() as u32)?;
+        for font in &self.fonts {
+            write_i32(w, font.ascent)?;
+            write_i32(w, font.descent)?;
+            let mut glyphs: Vec<_> = font.glyphs.iter().collect();
+            glyphs.sort_by_key(|(&c, _)| c);
+            write_u32(w, glyphs.len() as u32)?;
+            for (&c, glyph) in glyphs {
+                write_u32(w, c as u32)?;
+                write_rect(w, glyph.rect)?;
+                write_point(w, glyph.offset)?;
+                write_i32(w, glyph.advance)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads an atlas written by `write`, checking every rect lies inside the texture.
+    pub fn read(r: &mut impl Read) -> Result<Self, AtlasError> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if magic != ATLAS_MAGIC {
+            return Err(AtlasError::BadMagic);
+        }
+        let mut version = [0; 2];
+        r.read_exact(&mut version)?;
+        match u16::from_le_bytes(version) {
+            ATLAS_VERSION => (),
+            v => return Err(AtlasError::UnsupportedVersion(v)),
+        }
+
+        let (width, height) = (read_i32(r)?, read_i32(r)?);
+        if !(1..=ATLAS_MAX_SIZE).contains(&width) || !(1..=ATLAS_MAX_SIZE).contains(&height) {
+            return Err(AtlasError::InvalidSize(width, height));
+        }
+        let format = match read_u8(r)? {
+            0 => PixelFormat::Alpha,
+            1 => PixelFormat::Rgba,
+            format => return Err(AtlasError::InvalidFormat(format)),
+        };
+        let bpp = if format == PixelFormat::Rgba { 4 } else { 1 };
+        let len = (width as usize).checked_mul(height as usize).and_then(|n| n.checked_mul(bpp)).ok_or(AtlasError::InvalidSize(width, height))?;
+        // grown as the data arrives, so a truncated file fails without allocating the whole texture
+        let mut pixels = Vec::new();
+        r.by_ref().take(len as u64).read_to_end(&mut pixels)?;
+        if pixels.len() != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let inside = |r: Rect| {
+            let ok = r.x >= 0 && r.y >= 0 && r.w >= 0 && r.h >= 0 && r.x <= width - r.w && r.y <= height - r.h;
+            ok.then_some(r).ok_or(AtlasError::RectOutOfBounds(r))
+        };
+        let white = inside(read_rect(r)?)?;
+        let mut icons = Vec::new();
+        for _ in 0..read_u32(r)? {
+            icons.push(inside(read_rect(r)?)?);
+        }
+
+        let mut fonts = Vec::new();
+        for _ in 0..read_u32(r)? {
+            let mut font = FontFace {
+                ascent: read_i32(r)?,
+                descent: read_i32(r)?,
+                ..FontFace::default()
+            };
+            for _ in 0..read_u32(r)? {
+                let c = read_u32(r)?;
+                let c = char::from_u32(c).ok_or(AtlasError::InvalidChar(c))?;
+                let glyph = Glyph {
+                    rect: inside(read_rect(r)?)?,
+                    offset: read_point(r)?,
+                    advance: read_i32(r)?,
+                };
+                font.glyphs.insert(c, glyph);
+            }
+            fonts.push(font);
+        }
+
+        Ok(Self::from_parts(width, height, pixels, format, white, icons, fonts))
+    }
+}
@@ -0,0 +1,98 @@
+use crate::atlas::*;
+
+/// Skyline rect packer over an area of fixed width whose height can grow.
+///
+/// Each rect is placed at the lowest position the skyline of the rects placed before it leaves room for.
+#[derive(Clone)]
+pub struct Packer {
+    width: i32,
+    height: i32,
+    /// Segments of the skyline, left to right, as `(x, y, width)`.
+    skyline: Vec<(i32, i32, i32)>,
+}
+
+impl Packer {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            skyline: vec![(0, 0, width)],
+        }
+    }
+
+    /// A packer for an area in which `used` is already taken.
+    pub fn with_used(width: i32, height: i32, used: impl IntoIterator<Item = Rect>) -> Self {
+        let mut columns = vec![0; width.max(0) as usize];
+        for r in used {
+            for x in r.x.max(0)..(r.x + r.w).min(width) {
+                columns[x as usize] = columns[x as usize].max(r.y + r.h);
+            }
+        }
+        let mut skyline: Vec<(i32, i32, i32)> = Vec::new();
+        for (x, y) in columns.into_iter().enumerate() {
+            match skyline.last_mut() {
+                Some(seg) if seg.1 == y => seg.2 += 1,
+                _ => skyline.push((x as i32, y, 1)),
+            }
+        }
+        Self { width, height, skyline }
+    }
+
+    pub fn width(&self) -> i32 { self.width }
+
+    pub fn height(&self) -> i32 { self.height }
+
+    /// Extends the area downwards to `height`. Rects already placed are unaffected.
+    pub fn grow(&mut self, height: i32) { self.height = self.height.max(height); }
+
+    /// Finds room for a `w` by `h` rect, or `None` if it does not fit in the current height.
+    pub fn pack(&mut self, w: i32, h: i32) -> Option<Rect> {
+        let mut best: Option<(usize, i32)> = None;
+        for i in 0..self.skyline.len() {
+            let Some(y) = self.fit(i, w) else {
+                continue;
+            };
+            if y + h <= self.height && best.is_none_or(|(_, best_y)| y < best_y) {
+                best = Some((i, y));
+            }
+        }
+        let (i, y) = best?;
+        let r = rect(self.skyline[i].0, y, w, h);
+        self.place(i, r);
+        Some(r)
+    }
+
+    /// The height a `w` wide rect starting at segment `i` would rest at.
+    fn fit(&self, i: usize, w: i32) -> Option<i32> {
+        let x = self.skyline[i].0;
+        if x + w > self.width {
+            return None;
+        }
+        self.skyline[i..].iter().take_while(|s| s.0 < x + w).map(|s| s.1).max()
+    }
+
+    fn place(&mut self, i: usize, r: Rect) {
+        let right = r.x + r.w;
+        // drop or trim the segments the rect now covers
+        while let Some(&(sx, sy, sw)) = self.skyline.get(i).filter(|s| s.0 < right) {
+            if sx + sw <= right {
+                self.skyline.remove(i);
+            } else {
+                self.skyline[i] = (right, sy, sx + sw - right);
+                break;
+            }
+        }
+        self.skyline.insert(i, (r.x, r.y + r.h, r.w));
+
+        // merge neighbours at the same height
+        let mut k = 1;
+        while k < self.skyline.len() {
+            if self.skyline[k - 1].1 == self.skyline[k].1 {
+                self.skyline[k - 1].2 += self.skyline[k].2;
+                self.skyline.remove(k);
+            } else {
+                k += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::*;
+
+/// A renderer the command list can be dispatched to with `Context::render`.
+///
+/// Only rects, text and icons are required; the other primitives fall back to them or are skipped.
+pub trait Backend {
+    /// Called before a frame's commands with the context's scale factor, so text and icons can be drawn
+    /// from an atlas of matching density.
+    fn set_scale(&mut self, _scale: f32) {}
+
+    /// Scissors the following draws to `rect`, which is `Rect::UNCLIPPED` when no clipping is needed.
+    fn set_clip(&mut self, rect: Rect);
+
+    fn draw_rect(&mut self, rect: Rect, color: Color);
+
+    fn draw_text(&mut self, font: FontId, text: &str, pos: Vec2, color: Color);
+
+    fn draw_icon(&mut self, id: Icon, rect: Rect, color: Color);
+
+    fn draw_image(&mut self, _texture: TextureId, _rect: Rect, _uv: UvRect, _color: Color) {}
+
+    fn draw_line(&mut self, _p0: Vec2, _p1: Vec2, _thickness: i32, _color: Color) {}
+
+    fn draw_polyline(&mut self, points: &[Vec2], thickness: i32, color: Color) {
+        for seg in points.windows(2) {
+            self.draw_line(seg[0], seg[1], thickness, color);
+        }
+    }
+
+    fn draw_triangle(&mut self, _points: [Vec2; 3], _color: Color) {}
+
+    /// Fills `rect`, or outlines it `border` pixels thick when `border` is non-zero. Defaults to square corners.
+    fn draw_rounded_rect(&mut self, r: Rect, _radius: i32, border: i32, color: Color) {
+        if border == 0 {
+            self.draw_rect(r, color);
+            return;
+        }
+        self.draw_rect(rect(r.x + border, r.y, r.w - 2 * border, border), color);
+        self.draw_rect(rect(r.x + border, r.y + r.h - border, r.w - 2 * border, border), color);
+        self.draw_rect(rect(r.x, r.y, border, r.h), color);
+        self.draw_rect(rect(r.x + r.w - border, r.y, border, r.h), color);
+    }
+
+    /// Marks where the application draws the region reserved by `Context::custom_draw` with `id`.
+    fn draw_custom(&mut self, _id: u32, _rect: Rect) {}
+
+    /// Fills `rect` interpolating the corner colors. Defaults to a flat fill with the center color.
+    fn draw_gradient(&mut self, rect: Rect, radius: i32, colors: [Color; 4]) {
+        self.draw_rounded_rect(rect, radius, 0, colors[0].lerp(&colors[2], 0.5).lerp(&colors[1].lerp(&colors[3], 0.5), 0.5));
+    }
+}
+
+/// Dispatches `commands` to `backend`, calling `set_clip` whenever the clip rect changes.
+pub fn render_commands<'a>(backend: &mut impl Backend, commands: impl IntoIterator<Item = DrawCommand<'a>>) {
+    let mut clip = None;
+    for cmd in commands {
+        if clip != Some(cmd.clip()) {
+            clip = Some(cmd.clip());
+            backend.set_clip(cmd.clip());
+        }
+        match cmd {
+            DrawCommand::Rect { rect, color, .. } => backend.draw_rect(rect, color),
+            DrawCommand::Text { font, text, pos, color, .. } => backend.draw_text(font, text, pos, color),
+            DrawCommand::Icon { id, rect, color, .. } => backend.draw_icon(id, rect, color),
+            DrawCommand::Image { texture, rect, uv, color, .. } => backend.draw_image(texture, rect, uv, color),
+            DrawCommand::Line { p0, p1, thickness, color, .. } => backend.draw_line(p0, p1, thickness, color),
+            DrawCommand::Polyline { points, thickness, color, .. } => backend.draw_polyline(points, thickness, color),
+            DrawCommand::Triangle { points, color, .. } => backend.draw_triangle(points, color),
+            DrawCommand::RoundedRect { rect, radius, border, color, .. } => backend.draw_rounded_rect(rect, radius, border, color),
+            DrawCommand::Gradient { rect, radius, colors, .. } => backend.draw_gradient(rect, radius, colors),
+            DrawCommand::Custom { id, rect, .. } => backend.draw_custom(id, rect),
+        }
+    }
+}
+
+impl Context {
+    /// Draws the frame's commands, back to front, with `backend`.
+    pub fn render(&self, backend: &mut impl Backend) {
+        backend.set_scale(self.scale);
+        render_commands(backend, self.draw_commands())
+    }
+}
@@ -0,0 +1,271 @@
+use crate::atlas::Atlas;
+use crate::color::gradient_at;
+use crate::*;
+
+#[derive(Default, Copy, Clone)]
+#[repr(C)]
+pub struct Vertex {
+    pub pos: [f32; 2],
+    pub tex: [f32; 2],
+    pub color: Color,
+}
+
+/// A range of the index buffer sharing one scissor rect and texture. Indices are relative to `vertex_offset`.
+#[derive(Copy, Clone)]
+pub struct DrawCall {
+    pub clip: Rect,
+    /// `None` for the atlas, otherwise the texture of a `Command::Image`.
+    pub texture: Option<TextureId>,
+    pub vertex_offset: usize,
+    pub index_offset: usize,
+    pub index_count: usize,
+    /// `Some` for a `Command::Custom` region the application draws itself at this point. Such calls have no indices.
+    pub custom: Option<(u32, Rect)>,
+}
+
+/// Tessellates the command list into textured quads over the atlas, split into draw calls per clip rect.
+pub struct Batch<'a> {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u16>,
+    pub draw_calls: Vec<DrawCall>,
+    scale: f32,
+    atlas: &'a Atlas,
+    font: Option<&'a dyn Font>,
+}
+
+impl Default for Batch<'_> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            draw_calls: Vec::new(),
+            scale: 1.0,
+            atlas: Atlas::builtin(),
+            font: None,
+        }
+    }
+}
+
+impl<'a> Batch<'a> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Tessellates rects, text and icons against `atlas` instead of the built-in one.
+    pub fn set_atlas(&mut self, atlas: &'a Atlas) { self.atlas = atlas; }
+
+    /// Kerns text with `font` instead of the atlas, see `Atlas::layout_text`.
+    pub fn set_font(&mut self, font: &'a dyn Font) { self.font = Some(font); }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.draw_calls.clear();
+    }
+
+    /// Rebuilds the batch from the frame's commands.
+    pub fn build(&mut self, ctx: &Context) {
+        self.set_scale(ctx.scale);
+        self.build_commands(ctx.commands(), &ctx.text_stack, &ctx.point_stack)
+    }
+
+    /// Rebuilds the batch from `commands`, whose text and polyline points index into `text` and `points`.
+    pub fn build_commands<'c>(&mut self, commands: impl IntoIterator<Item = &'c Command>, text: &str, points: &[Vec2]) {
+        self.clear();
+        crate::backend::render_commands(self, commands.into_iter().map(|cmd| DrawCommand::resolve(cmd, text, points)));
+        if self.draw_calls.last().is_some_and(|call| call.index_count == 0 && call.custom.is_none()) {
+            self.draw_calls.pop();
+        }
+    }
+
+    fn set_texture(&mut self, texture: Option<TextureId>) {
+        match self.draw_calls.last_mut() {
+            Some(call) if call.index_count == 0 && call.custom.is_none() => call.texture = texture,
+            Some(call) if call.texture == texture && call.custom.is_none() => (),
+            Some(call) => {
+                let clip = call.clip;
+                self.new_draw_call(clip, texture)
+            }
+            None => self.new_draw_call(Rect::UNCLIPPED, texture),
+        }
+    }
+
+    /// Pushes a quad covering `dst`, textured with the `src` region of the atlas.
+    pub fn push_rect(&mut self, dst: Rect, src: Rect, color: Color) {
+        let x = src.x as f32 / self.atlas.width as f32;
+        let y = src.y as f32 / self.atlas.height as f32;
+        let w = src.w as f32 / self.atlas.width as f32;
+        let h = src.h as f32 / self.atlas.height as f32;
+
+        self.set_texture(None);
+        self.push_quad(dst, uv_rect(x, y, x + w, y + h), color);
+    }
+
+    fn push_quad(&mut self, dst: Rect, uv: UvRect, color: Color) {
+        let (x0, y0) = (dst.x as f32, dst.y as f32);
+        let (x1, y1) = (x0 + dst.w as f32, y0 + dst.h as f32);
+        self.push_vertices(
+            &[
+                Vertex { pos: [x0, y0], tex: [uv.u0, uv.v0], color },
+                Vertex { pos: [x1, y0], tex: [uv.u1, uv.v0], color },
+                Vertex { pos: [x1, y1], tex: [uv.u1, uv.v1], color },
+                Vertex { pos: [x0, y1], tex: [uv.u0, uv.v1], color },
+            ],
+            &[0, 1, 2, 2, 3, 0],
+        );
+    }
+
+    /// Pushes untextured geometry, sampling the white texel of the atlas.
+    fn push_solid(&mut self, positions: &[[f32; 2]], indices: &[u16], color: Color) {
+        let tex = white_texel(self.atlas);
+        let verts: Vec<Vertex> = positions.iter().map(|&pos| Vertex { pos, tex, color }).collect();
+        self.set_texture(None);
+        self.push_vertices(&verts, indices);
+    }
+
+    /// Appends `verts` and `indices` (relative to the first of `verts`) to the current draw call.
+    fn push_vertices(&mut self, verts: &[Vertex], indices: &[u16]) {
+        let call = *self.draw_calls.last().unwrap();
+        if self.vertices.len() - call.vertex_offset + verts.len() > u16::MAX as usize + 1 {
+            self.new_draw_call(call.clip, call.texture);
+        }
+        let call = self.draw_calls.last_mut().unwrap();
+
+        let is = (self.vertices.len() - call.vertex_offset) as u16;
+        self.indices.extend(indices.iter().map(|i| is + i));
+        self.vertices.extend_from_slice(verts);
+        call.index_count += indices.len();
+    }
+
+    fn new_draw_call(&mut self, clip: Rect, texture: Option<TextureId>) {
+        self.draw_calls.push(DrawCall {
+            clip,
+            texture,
+            vertex_offset: self.vertices.len(),
+            index_offset: self.indices.len(),
+            index_count: 0,
+            custom: None,
+        });
+    }
+}
+
+impl Backend for Batch<'_> {
+    /// Glyphs and icons are scaled up from the atlas.
+    fn set_scale(&mut self, scale: f32) { self.scale = scale; }
+
+    fn set_clip(&mut self, clip: Rect) {
+        match self.draw_calls.last_mut() {
+            Some(call) if call.index_count == 0 && call.custom.is_none() => call.clip = clip,
+            Some(call) if call.clip == clip => (),
+            Some(call) => {
+                let texture = call.texture;
+                self.new_draw_call(clip, texture)
+            }
+            None => self.new_draw_call(clip, None),
+        }
+    }
+
+    fn draw_rect(&mut self, rect: Rect, color: Color) { self.push_rect(rect, self.atlas.white, color); }
+
+    fn draw_text(&mut self, font: FontId, text: &str, pos: Vec2, color: Color) {
+        let atlas = self.atlas;
+        for (dst, src) in atlas.layout_text(self.font.unwrap_or(atlas), font, text, pos, self.scale) {
+            self.push_rect(dst, src, color);
+        }
+    }
+
+    fn draw_icon(&mut self, id: Icon, r: Rect, color: Color) {
+        let (dst, src) = self.atlas.layout_icon(id, r, self.scale);
+        self.push_rect(dst, src, color);
+    }
+
+    fn draw_image(&mut self, texture: TextureId, dst: Rect, uv: UvRect, color: Color) {
+        self.set_texture(Some(texture));
+        self.push_quad(dst, uv, color);
+    }
+
+    fn draw_line(&mut self, p0: Vec2, p1: Vec2, thickness: i32, color: Color) {
+        let Some(quad) = line_quad(p0, p1, thickness) else {
+            return;
+        };
+        self.push_solid(&quad, &[0, 1, 2, 2, 3, 0], color);
+    }
+
+    fn draw_triangle(&mut self, points: [Vec2; 3], color: Color) { self.push_solid(&points.map(|p| [p.x as f32, p.y as f32]), &[0, 1, 2], color); }
+
+    /// Fills `r` with rounded corners, or only a `border` pixels thick outline of it when `border` is non-zero.
+    fn draw_rounded_rect(&mut self, r: Rect, radius: i32, border: i32, color: Color) {
+        let segments = (radius as usize / 2).clamp(2, 12);
+        let outer = rounded_outline(r, radius, segments);
+        let n = outer.len() as u16;
+        let inner = r.expand(-border);
+        if border > 0 && inner.w > 0 && inner.h > 0 {
+            let mut positions = outer;
+            positions.extend(rounded_outline(inner, (radius - border).max(0), segments));
+            let indices: Vec<u16> = (0..n).flat_map(|i| [i, (i + 1) % n, n + (i + 1) % n, n + (i + 1) % n, n + i, i]).collect();
+            self.push_solid(&positions, &indices, color);
+        } else {
+            let mut positions = vec![[r.x as f32 + r.w as f32 / 2.0, r.y as f32 + r.h as f32 / 2.0]];
+            positions.extend(outer);
+            let indices: Vec<u16> = (0..n).flat_map(|i| [0, i + 1, (i + 1) % n + 1]).collect();
+            self.push_solid(&positions, &indices, color);
+        }
+    }
+
+    fn draw_gradient(&mut self, r: Rect, radius: i32, colors: [Color; 4]) {
+        let (positions, indices): (Vec<[f32; 2]>, Vec<u16>) = if radius > 0 {
+            let outline = rounded_outline(r, radius, (radius as usize / 2).clamp(2, 12));
+            let n = outline.len() as u16;
+            let center = [r.x as f32 + r.w as f32 / 2.0, r.y as f32 + r.h as f32 / 2.0];
+            (std::iter::once(center).chain(outline).collect(), (0..n).flat_map(|i| [0, i + 1, (i + 1) % n + 1]).collect())
+        } else {
+            let (x0, y0, x1, y1) = (r.x as f32, r.y as f32, (r.x + r.w) as f32, (r.y + r.h) as f32);
+            (vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1]], vec![0, 1, 2, 2, 3, 0])
+        };
+        let tex = white_texel(self.atlas);
+        let verts: Vec<Vertex> = positions
+            .iter()
+            .map(|&pos| Vertex {
+                pos,
+                tex,
+                color: gradient_at(&colors, r, pos[0], pos[1]),
+            })
+            .collect();
+        self.set_texture(None);
+        self.push_vertices(&verts, &indices);
+    }
+
+    fn draw_custom(&mut self, id: u32, rect: Rect) {
+        match self.draw_calls.last_mut() {
+            Some(call) if call.index_count == 0 && call.custom.is_none() => call.custom = Some((id, rect)),
+            last => {
+                let clip = last.map_or(Rect::UNCLIPPED, |call| call.clip);
+                self.new_draw_call(clip, None);
+                self.draw_calls.last_mut().unwrap().custom = Some((id, rect));
+            }
+        }
+    }
+}
+
+/// UV of the center of the atlas' white patch, used for untextured geometry.
+fn white_texel(atlas: &Atlas) -> [f32; 2] {
+    let white = atlas.white;
+    [
+        (white.x as f32 + white.w as f32 / 2.0) / atlas.width as f32,
+        (white.y as f32 + white.h as f32 / 2.0) / atlas.height as f32,
+    ]
+}
+
+/// Clockwise outline of `r` with rounded corners, `segments + 1` points per corner starting at the top left.
+fn rounded_outline(r: Rect, radius: i32, segments: usize) -> Vec<[f32; 2]> {
+    let rad = radius as f32;
+    let (x0, y0) = (r.x as f32 + rad, r.y as f32 + rad);
+    let (x1, y1) = ((r.x + r.w) as f32 - rad, (r.y + r.h) as f32 - rad);
+    let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)];
+    let mut res = Vec::with_capacity(4 * (segments + 1));
+    for (i, (cx, cy)) in corners.into_iter().enumerate() {
+        for s in 0..=segments {
+            let angle = std::f32::consts::FRAC_PI_2 * (i as f32 + 2.0 + s as f32 / segments as f32);
+            res.push([cx + rad * angle.cos(), cy + rad * angle.sin()]);
+        }
+    }
+    res
+}
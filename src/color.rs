@@ -1,3 +1,4 @@
+use crate::Rect;
 use std::ops::Mul;
 
 #[repr(C)]
@@ -330,15 +331,16 @@ impl Color {
         }
     }
 
-    // pub fn lerp(&self, other: &Self, amt: f32) -> Self {
-    //     let amt = amt.clamp(0.0, 1.0);
-    //     Self {
-    //         r: lerp(self.r, amt, other.r),
-    //         g: lerp(self.g, amt, other.g),
-    //         b: lerp(self.b, amt, other.b),
-    //         a: lerp(self.a, amt, other.a),
-    //     }
-    // }
+    pub fn lerp(&self, other: &Self, amt: f32) -> Self {
+        let amt = amt.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amt + 0.5) as u8;
+        Self {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
 
     pub const fn to_hex(&self) -> u32 {
         let r = self.r as u32;
@@ -384,3 +386,10 @@ impl From<[u8; 4]> for Color {
 impl From<Color> for [u8; 4] {
     fn from(c: Color) -> Self { [c.r, c.g, c.b, c.a] }
 }
+
+/// Bilinearly interpolates the corner colors (top-left, top-right, bottom-right, bottom-left) of `r` at `x,y`.
+pub(crate) fn gradient_at(colors: &[Color; 4], r: Rect, x: f32, y: f32) -> Color {
+    let u = (x - r.x as f32) / r.w.max(1) as f32;
+    let v = (y - r.y as f32) / r.h.max(1) as f32;
+    colors[0].lerp(&colors[1], u).lerp(&colors[3].lerp(&colors[2], u), v)
+}
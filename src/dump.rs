@@ -0,0 +1,89 @@
+use crate::*;
+use std::fmt::{self, Write};
+
+struct R(Rect);
+
+impl fmt::Display for R {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{},{} {}x{}", self.0.x, self.0.y, self.0.w, self.0.h) }
+}
+
+struct P(Vec2);
+
+impl fmt::Display for P {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{},{}", self.0.x, self.0.y) }
+}
+
+struct C(Color);
+
+impl fmt::Display for C {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "#{:08x}", self.0.to_hex()) }
+}
+
+struct I(Icon);
+
+impl fmt::Display for I {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.0 {
+            Icon::Close => "close",
+            Icon::Check => "check",
+            Icon::Collapsed => "collapsed",
+            Icon::Expanded => "expanded",
+            Icon::ArrowUp => "arrow-up",
+            Icon::ArrowDown => "arrow-down",
+            Icon::ArrowLeft => "arrow-left",
+            Icon::ArrowRight => "arrow-right",
+            Icon::Plus => "plus",
+            Icon::Minus => "minus",
+            Icon::Radio => "radio",
+            Icon::ResizeGrip => "resize-grip",
+            Icon::Menu => "menu",
+            Icon::Search => "search",
+            Icon::Warning => "warning",
+            Icon::User(n) => return write!(f, "user{}", n),
+        };
+        f.write_str(name)
+    }
+}
+
+fn write_command(out: &mut impl Write, cmd: DrawCommand) -> fmt::Result {
+    match cmd {
+        DrawCommand::Rect { rect, color, .. } => write!(out, "rect {} {}", R(rect), C(color)),
+        DrawCommand::Text { font, text, pos, color, .. } => write!(out, "text {:?} @ {},{} {} font {}", text, pos.x, pos.y, C(color), font.0),
+        DrawCommand::Icon { rect, id, color, .. } => write!(out, "icon {} {} {}", I(id), R(rect), C(color)),
+        DrawCommand::Image { texture, rect, uv, color, .. } => {
+            write!(out, "image {} {} uv {},{} {},{} {}", texture.0, R(rect), uv.u0, uv.v0, uv.u1, uv.v1, C(color))
+        }
+        DrawCommand::Line { p0, p1, thickness, color, .. } => write!(out, "line {} {} w{} {}", P(p0), P(p1), thickness, C(color)),
+        DrawCommand::Polyline { points, thickness, color, .. } => {
+            write!(out, "polyline")?;
+            for &p in points {
+                write!(out, " {}", P(p))?;
+            }
+            write!(out, " w{} {}", thickness, C(color))
+        }
+        DrawCommand::Triangle { points: [p0, p1, p2], color, .. } => write!(out, "triangle {} {} {} {}", P(p0), P(p1), P(p2), C(color)),
+        DrawCommand::RoundedRect { rect, radius, border: 0, color, .. } => write!(out, "rrect {} r{} {}", R(rect), radius, C(color)),
+        DrawCommand::RoundedRect { rect, radius, border, color, .. } => write!(out, "rrect {} r{} w{} {}", R(rect), radius, border, C(color)),
+        DrawCommand::Gradient { rect, radius, colors: [tl, tr, br, bl], .. } => {
+            write!(out, "gradient {} r{} {} {} {} {}", R(rect), radius, C(tl), C(tr), C(br), C(bl))
+        }
+        DrawCommand::Custom { id, rect, .. } => write!(out, "custom {} {}", id, R(rect)),
+    }?;
+    if cmd.clip() != Rect::UNCLIPPED {
+        write!(out, " clip {}", R(cmd.clip()))?;
+    }
+    writeln!(out)
+}
+
+pub(crate) fn dump<'a>(commands: impl IntoIterator<Item = DrawCommand<'a>>) -> String {
+    let mut res = String::new();
+    for cmd in commands {
+        let _ = write_command(&mut res, cmd);
+    }
+    res
+}
+
+impl Context {
+    /// Serializes the frame's commands, in render order, one per line, e.g. `rect 10,10 300x450 #323232ff`.
+    pub fn dump_commands(&self) -> String { dump(self.draw_commands()) }
+}
@@ -0,0 +1,66 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::atlas::*;
+use crate::*;
+
+/// Text metrics for the fonts referenced by `FontId`, in logical pixels.
+///
+/// Implementations may hold the loaded font data; only `char_width` and `font_height` are required.
+pub trait Font {
+    /// Horizontal advance of `c`.
+    fn char_width(&self, font: FontId, c: char) -> i32;
+
+    /// Height of a line of text without the line gap, i.e. ascent plus descent.
+    fn font_height(&self, font: FontId) -> i32;
+
+    /// Adjustment added to the advance of `left` when it is followed by `right`.
+    fn kerning(&self, _font: FontId, _left: char, _right: char) -> i32 { 0 }
+
+    fn ascent(&self, font: FontId) -> i32 { self.font_height(font) - self.descent(font) }
+
+    fn descent(&self, _font: FontId) -> i32 { 0 }
+
+    /// Extra space between consecutive lines.
+    fn line_gap(&self, _font: FontId) -> i32 { 0 }
+
+    fn line_height(&self, font: FontId) -> i32 { self.font_height(font) + self.line_gap(font) }
+}
+
+/// Metrics of the font baked into `atlas::ATLAS_TEXTURE`, used for every `FontId`.
+#[derive(Copy, Clone, Default)]
+pub struct AtlasFont;
+
+impl Font for AtlasFont {
+    fn char_width(&self, _font: FontId, c: char) -> i32 {
+        match c as usize {
+            c if c < 127 => ATLAS[ATLAS_FONT as usize + c].w,
+            _ => 0,
+        }
+    }
+
+    fn font_height(&self, _font: FontId) -> i32 { 18 }
+}
+
+macro_rules! forward_font {
+    ($($ty:ty),*) => {$(
+        impl<F: Font + ?Sized> Font for $ty {
+            fn char_width(&self, font: FontId, c: char) -> i32 { (**self).char_width(font, c) }
+
+            fn font_height(&self, font: FontId) -> i32 { (**self).font_height(font) }
+
+            fn kerning(&self, font: FontId, left: char, right: char) -> i32 { (**self).kerning(font, left, right) }
+
+            fn ascent(&self, font: FontId) -> i32 { (**self).ascent(font) }
+
+            fn descent(&self, font: FontId) -> i32 { (**self).descent(font) }
+
+            fn line_gap(&self, font: FontId) -> i32 { (**self).line_gap(font) }
+
+            fn line_height(&self, font: FontId) -> i32 { (**self).line_height(font) }
+        }
+    )*};
+}
+
+// lets the context share an `Atlas` with the backends drawing from it
+forward_font!(&F, Box<F>, Rc<F>, Arc<F>);
@@ -20,45 +20,250 @@ impl MouseButton {
 
 bitflags! {
     #[derive(Clone, Copy)]
-    pub struct KeyMode : u32 {
-        const SHIFT     = 1 << 0;
-        const CTRL      = 1 << 1;
-        const ALT       = 1 << 2;
-        const BACKSPACE = 1 << 3;
-        const RETURN    = 1 << 4;
+    pub struct Modifiers : u32 {
+        const SHIFT = 1 << 0;
+        const CTRL  = 1 << 1;
+        const ALT   = 1 << 2;
+        const SUPER = 1 << 3;
     }
 }
 
-impl KeyMode {
+impl Modifiers {
     pub const fn is_none(&self) -> bool { self.is_empty() }
-    pub const fn is_return(&self) -> bool { self.intersects(Self::RETURN) }
-    pub const fn is_backspace(&self) -> bool { self.intersects(Self::BACKSPACE) }
     pub const fn is_alt(&self) -> bool { self.intersects(Self::ALT) }
     pub const fn is_ctrl(&self) -> bool { self.intersects(Self::CTRL) }
     pub const fn is_shift(&self) -> bool { self.intersects(Self::SHIFT) }
+    pub const fn is_super(&self) -> bool { self.intersects(Self::SUPER) }
 }
 
-impl Context {
-    pub fn input_mousemove(&mut self, x: i32, y: i32) { self.mouse_pos = vec2(x, y); }
+bitflags! {
+    /// Directional navigation input from a gamepad or, with `Context::key_nav`, the arrow keys and Escape.
+    #[derive(Clone, Copy)]
+    pub struct NavInput : u32 {
+        const UP       = 1 << 0;
+        const DOWN     = 1 << 1;
+        const LEFT     = 1 << 2;
+        const RIGHT    = 1 << 3;
+        const ACTIVATE = 1 << 4;
+        const CANCEL   = 1 << 5;
+    }
+}
+
+impl NavInput {
+    pub const fn is_none(&self) -> bool { self.is_empty() }
+    pub const fn is_activate(&self) -> bool { self.intersects(Self::ACTIVATE) }
+    pub const fn is_cancel(&self) -> bool { self.intersects(Self::CANCEL) }
+}
+
+/// A key that widgets or the application may react to. Modifier keys are reported with `Context::input_modifiers`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Key {
+    Backspace,
+    Return,
+    Tab,
+    Escape,
+    Space,
+    Delete,
+    Insert,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Num0,
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    Num6,
+    Num7,
+    Num8,
+    Num9,
+}
+
+/// One piece of input, queued with `Context::push_event` and applied in order at the start of a frame.
+#[derive(Clone)]
+pub enum InputEvent {
+    /// Position in physical pixels.
+    MouseMove(i32, i32),
+    MouseDown(MouseButton),
+    MouseUp(MouseButton),
+    Scroll(i32, i32),
+    KeyDown(Key),
+    KeyUp(Key),
+    /// The modifier keys now held.
+    Modifiers(Modifiers),
+    Text(String),
+    Nav(NavInput),
+}
+
+/// An `InputEvent` with the time it happened, in milliseconds on the application's clock.
+#[derive(Clone)]
+pub struct TimedEvent {
+    pub time: u64,
+    pub event: InputEvent,
+}
+
+/// A set of `Key`s.
+#[derive(Default, PartialEq, Copy, Clone)]
+pub struct Keys(u64);
 
-    pub fn input_mousedown(&mut self, btn: MouseButton) {
-        self.mouse_down |= btn;
-        self.mouse_pressed |= btn;
+impl Keys {
+    pub const fn empty() -> Self { Self(0) }
+    pub const fn is_empty(&self) -> bool { self.0 == 0 }
+    pub const fn contains(&self, key: Key) -> bool { self.0 & (1 << key as u64) != 0 }
+    pub fn insert(&mut self, key: Key) { self.0 |= 1 << key as u64; }
+    pub fn remove(&mut self, key: Key) { self.0 &= !(1 << key as u64); }
+}
+
+impl Context {
+    /// Queues `event`, which happened at `time` milliseconds, behind every event pushed before it. A mouse button
+    /// pressed and released in the same frame, and keys typed after a mouse press in it, wait for the next frame so no
+    /// click or keystroke is lost. The `input_*` functions queue their events at the time of the last one pushed.
+    pub fn push_event(&mut self, event: InputEvent, time: u64) {
+        self.event_time = time;
+        self.event_queue.push_back(TimedEvent { time, event });
     }
 
-    pub fn input_mouseup(&mut self, btn: MouseButton) { self.mouse_down &= !btn; }
+    /// Events applied this frame, in the order they happened.
+    pub fn input_events(&self) -> impl Iterator<Item = &TimedEvent> { self.input_events.iter().map(|e| &e.0) }
+
+    /// Whether events are queued for later frames, such as the release of a button pressed this frame.
+    pub fn has_pending_events(&self) -> bool { !self.event_queue.is_empty() }
+
+    fn push_untimed(&mut self, event: InputEvent) { self.push_event(event, self.event_time); }
+
+    /// Takes the position in physical pixels.
+    pub fn input_mousemove(&mut self, x: i32, y: i32) { self.push_untimed(InputEvent::MouseMove(x, y)); }
+
+    pub fn input_mousedown(&mut self, btn: MouseButton) { self.push_untimed(InputEvent::MouseDown(btn)); }
 
-    pub fn input_scroll(&mut self, x: i32, y: i32) {
-        self.scroll_delta.x += x;
-        self.scroll_delta.y += y;
+    pub fn input_mouseup(&mut self, btn: MouseButton) { self.push_untimed(InputEvent::MouseUp(btn)); }
+
+    pub fn input_scroll(&mut self, x: i32, y: i32) { self.push_untimed(InputEvent::Scroll(x, y)); }
+
+    /// Key repeats may be reported as further `input_keydown` calls.
+    pub fn input_keydown(&mut self, key: Key) { self.push_untimed(InputEvent::KeyDown(key)); }
+
+    pub fn input_keyup(&mut self, key: Key) { self.push_untimed(InputEvent::KeyUp(key)); }
+
+    /// Sets the modifier keys currently held.
+    pub fn input_modifiers(&mut self, mods: Modifiers) { self.push_untimed(InputEvent::Modifiers(mods)); }
+
+    pub fn input_text(&mut self, text: &str) { self.push_untimed(InputEvent::Text(text.to_string())); }
+
+    /// Applies queued events to the input state in order, into `input_events`. Tab, navigation and activation keys
+    /// act on focus as they are applied, so each later key goes to the control focused at that point. Stops before a
+    /// mouse button that already changed this frame, and before a key, text or navigation event following a mouse
+    /// press, since the control the press focuses is only known once the widgets have run.
+    pub(crate) fn apply_events(&mut self) {
+        let mut buttons = MouseButton::empty();
+        while let Some(timed) = self.event_queue.front() {
+            let focus = self.focus;
+            match timed.event {
+                InputEvent::MouseMove(x, y) => self.mouse_pos = vec2((x as f32 / self.scale).floor() as i32, (y as f32 / self.scale).floor() as i32),
+                InputEvent::MouseDown(btn) if !buttons.intersects(btn) => {
+                    buttons |= btn;
+                    self.mouse_down |= btn;
+                    self.mouse_pressed |= btn;
+                    self.mouse_press_pos = self.mouse_pos;
+                }
+                InputEvent::MouseUp(btn) if !buttons.intersects(btn) => {
+                    buttons |= btn;
+                    self.mouse_down &= !btn;
+                }
+                InputEvent::Scroll(x, y) => {
+                    self.scroll_delta.x += x;
+                    self.scroll_delta.y += y;
+                }
+                InputEvent::Modifiers(mods) => self.modifiers = mods,
+                _ if !self.mouse_pressed.is_none() => break,
+                InputEvent::KeyDown(key) => {
+                    self.key_pressed.insert(key);
+                    self.key_down.insert(key);
+                    self.apply_key(key);
+                }
+                InputEvent::KeyUp(key) => self.key_down.remove(key),
+                InputEvent::Text(ref text) => self.input_text += text,
+                InputEvent::Nav(nav) => self.apply_nav(nav),
+                _ => break,
+            }
+            self.input_events.extend(self.event_queue.pop_front().map(|e| (e, focus)));
+        }
     }
 
-    pub fn input_keydown(&mut self, key: KeyMode) {
-        self.key_pressed |= key;
-        self.key_down |= key;
+    /// Moves focus on Tab and, with `key_nav`, the arrow keys, and activates the focused control on Return or Space.
+    fn apply_key(&mut self, key: Key) {
+        // a textbox being edited keeps Left, Right and Escape for itself
+        let editing = self.focus.is_some() && self.focus == self.text_edit;
+        match key {
+            Key::Tab => self.move_focus(if self.modifiers.is_shift() { -1 } else { 1 }),
+            Key::Return | Key::Space if self.focus_visible => self.key_activated.extend(self.focus),
+            Key::Up if self.key_nav => self.apply_nav(NavInput::UP),
+            Key::Down if self.key_nav => self.apply_nav(NavInput::DOWN),
+            Key::Left if self.key_nav && !editing => self.apply_nav(NavInput::LEFT),
+            Key::Right if self.key_nav && !editing => self.apply_nav(NavInput::RIGHT),
+            Key::Escape if self.key_nav && !editing => self.apply_nav(NavInput::CANCEL),
+            _ => (),
+        }
     }
 
-    pub fn input_keyup(&mut self, key: KeyMode) { self.key_down &= !key; }
+    fn apply_nav(&mut self, nav: NavInput) {
+        self.nav_pressed |= nav;
+        let dirs = [(NavInput::UP, vec2(0, -1)), (NavInput::DOWN, vec2(0, 1)), (NavInput::LEFT, vec2(-1, 0)), (NavInput::RIGHT, vec2(1, 0))];
+        for (dir_nav, dir) in dirs {
+            if nav.contains(dir_nav) {
+                self.move_focus_toward(dir);
+            }
+        }
+        if nav.is_activate() {
+            self.key_activated.extend(self.focus);
+        }
+    }
 
-    pub fn input_text(&mut self, text: &str) { self.input_text += text; }
+    /// Reports a gamepad button press. Directions move focus to the nearest control that way, `ACTIVATE` clicks the
+    /// focused control and `CANCEL` is left for the application to read with `Context::nav_cancelled`.
+    pub fn input_nav(&mut self, nav: NavInput) { self.push_untimed(InputEvent::Nav(nav)); }
 }
@@ -53,9 +53,9 @@ impl Context {
         self.layout_row(&[0], 0);
     }
 
-    pub(crate) fn get_layout(&self) -> &Layout { return self.layout_stack.last().unwrap(); }
+    pub(crate) fn get_layout(&self) -> &Layout { self.layout_stack.last().unwrap() }
 
-    pub(crate) fn get_layout_mut(&mut self) -> &mut Layout { return self.layout_stack.last_mut().unwrap(); }
+    pub(crate) fn get_layout_mut(&mut self) -> &mut Layout { self.layout_stack.last_mut().unwrap() }
 
     pub fn layout_begin_column(&mut self) {
         let layout = self.layout_next();
@@ -1,18 +1,26 @@
-#![feature(portable_simd)]
-
 use self::pool::Pool;
 use bitflags::*;
+use std::collections::VecDeque;
 use std::fmt::Write;
 
 pub mod atlas;
+mod backend;
+pub mod batch;
 mod color;
+mod dump;
+mod font;
 mod input;
 mod layout;
 mod math;
 mod pool;
+pub mod raster;
+pub mod snapshot;
 mod util;
 mod widget;
+pub mod wire;
 
+pub use self::backend::*;
+pub use self::font::*;
 pub use self::input::*;
 pub use self::layout::*;
 pub use self::math::*;
@@ -43,6 +51,8 @@ pub enum ControlColor {
     BaseFocus,
     ScrollBase,
     ScrollThumb,
+    Shadow,
+    FocusRing,
 }
 
 impl ControlColor {
@@ -67,10 +77,59 @@ impl ControlColor {
 
 #[derive(PartialEq, Copy, Clone)]
 pub enum Icon {
-    Close = 1,
+    Close,
     Check,
     Collapsed,
     Expanded,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Plus,
+    Minus,
+    Radio,
+    ResizeGrip,
+    Menu,
+    Search,
+    Warning,
+    /// An application icon registered with `AtlasBuilder::icon`, numbered from 0 in registration order.
+    User(u16),
+}
+
+impl Icon {
+    /// Every icon baked into the atlas, in `atlas::ATLAS_ICONS` order.
+    pub const BUILTIN: [Icon; 15] = [
+        Self::Close,
+        Self::Check,
+        Self::Collapsed,
+        Self::Expanded,
+        Self::ArrowUp,
+        Self::ArrowDown,
+        Self::ArrowLeft,
+        Self::ArrowRight,
+        Self::Plus,
+        Self::Minus,
+        Self::Radio,
+        Self::ResizeGrip,
+        Self::Menu,
+        Self::Search,
+        Self::Warning,
+    ];
+
+    /// Position of the icon's rect in `Atlas::icons`, where application icons follow the built-in ones.
+    pub fn index(self) -> usize {
+        match self {
+            Self::User(n) => Self::BUILTIN.len() + 1 + n as usize,
+            icon => Self::BUILTIN.iter().position(|&b| b == icon).unwrap() + 1,
+        }
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index.checked_sub(1)? {
+            i if i < Self::BUILTIN.len() => Some(Self::BUILTIN[i]),
+            i => u16::try_from(i - Self::BUILTIN.len()).ok().map(Self::User),
+        }
+    }
 }
 
 bitflags! {
@@ -89,11 +148,22 @@ impl ResourceState {
 }
 
 pub struct Context {
-    pub char_width: fn(FontId, char) -> usize,
-    pub font_height: fn(FontId) -> usize,
+    /// Metrics for every `FontId` used by the style and widgets.
+    pub fonts: Box<dyn Font>,
     pub style: Style,
+    /// Physical pixels per logical pixel. Style, layout and widgets work in logical pixels; commands and
+    /// mouse input are in physical pixels.
+    pub scale: f32,
     pub hover: Option<Id>,
     pub focus: Option<Id>,
+    /// Set when `focus` was last moved from the keyboard: the focused control shows a focus ring and keeps focus
+    /// without the mouse held on it.
+    pub focus_visible: bool,
+    /// Interactive controls of the current frame in the order they were updated, with the rect and root container of each.
+    pub focus_order: Vec<(Id, Rect, Option<usize>)>,
+    /// Treats the arrow keys as `NavInput` directions and Escape as `NavInput::CANCEL`. While a textbox is being
+    /// edited only Up and Down navigate.
+    pub key_nav: bool,
     pub last_id: Option<Id>,
     pub last_rect: Rect,
     pub last_zindex: i32,
@@ -104,13 +174,26 @@ pub struct Context {
     pub scroll_target: Option<usize>,
     pub number_edit_buf: String,
     pub number_edit: Option<Id>,
+    /// The textbox `text_cursor` belongs to.
+    pub text_edit: Option<Id>,
+    /// Byte offset of the caret in the focused textbox.
+    pub text_cursor: usize,
     pub command_list: Vec<Command>,
+    /// Commands drawn after every root container, see `Context::overlay`.
+    pub overlay_list: Vec<Command>,
+    pub in_overlay: bool,
+    /// Rects of the interactive controls updated inside `Context::overlay` last frame. Input over them goes to
+    /// the overlay instead of the windows beneath.
+    pub overlay_rects: Vec<Rect>,
+    /// Whether the mouse, or the press this frame, is over one of `overlay_rects`.
+    pub overlay_hover: bool,
     pub root_list: Vec<usize>,
     pub container_stack: Vec<usize>,
     pub clip_stack: Vec<Rect>,
     pub id_stack: Vec<Id>,
     pub layout_stack: Vec<Layout>,
     pub text_stack: String,
+    pub point_stack: Vec<Vec2>,
     pub container_pool: Pool<48>,
     pub containers: [Container; 48],
     pub treenode_pool: Pool<48>,
@@ -120,9 +203,21 @@ pub struct Context {
     pub scroll_delta: Vec2,
     pub mouse_down: MouseButton,
     pub mouse_pressed: MouseButton,
-    pub key_down: KeyMode,
-    pub key_pressed: KeyMode,
+    /// Where the mouse was when a button was last pressed.
+    pub mouse_press_pos: Vec2,
+    pub key_down: Keys,
+    pub key_pressed: Keys,
+    pub modifiers: Modifiers,
+    pub nav_pressed: NavInput,
+    /// Controls activated this frame with Return, Space or `NavInput::ACTIVATE`, in order.
+    pub key_activated: Vec<Id>,
     pub input_text: String,
+    /// Events pushed but not yet applied, see `Context::push_event`.
+    pub(crate) event_queue: VecDeque<TimedEvent>,
+    /// Time of the last event pushed, for events queued without one.
+    pub(crate) event_time: u64,
+    /// Events applied this frame in the order they happened, each with the control focused when it did.
+    pub(crate) input_events: Vec<(TimedEvent, Option<Id>)>,
 }
 
 #[derive(Default, Copy, Clone)]
@@ -137,14 +232,13 @@ pub struct Container {
     pub open: bool,
 }
 
+/// A draw command. `clip` is the rect the command must be scissored to, or `Rect::UNCLIPPED` when it lies fully inside it.
 #[derive(Copy, Clone)]
 pub enum Command {
-    Clip {
-        rect: Rect,
-    },
     Rect {
         rect: Rect,
         color: Color,
+        clip: Rect,
     },
     Text {
         font: FontId,
@@ -152,17 +246,285 @@ pub enum Command {
         color: Color,
         str_start: usize,
         str_len: usize,
+        clip: Rect,
     },
     Icon {
         rect: Rect,
         id: Icon,
         color: Color,
+        clip: Rect,
+    },
+    Image {
+        texture: TextureId,
+        rect: Rect,
+        uv: UvRect,
+        color: Color,
+        clip: Rect,
+    },
+    Line {
+        p0: Vec2,
+        p1: Vec2,
+        thickness: i32,
+        color: Color,
+        clip: Rect,
+    },
+    Polyline {
+        points_start: usize,
+        points_len: usize,
+        thickness: i32,
+        color: Color,
+        clip: Rect,
+    },
+    Triangle {
+        points: [Vec2; 3],
+        color: Color,
+        clip: Rect,
+    },
+    /// A rect with rounded corners, filled when `border` is 0 and outlined `border` pixels thick otherwise.
+    RoundedRect {
+        rect: Rect,
+        radius: i32,
+        border: i32,
+        color: Color,
+        clip: Rect,
+    },
+    /// A filled rect whose color is interpolated between its corners: top-left, top-right, bottom-right, bottom-left.
+    Gradient {
+        rect: Rect,
+        radius: i32,
+        colors: [Color; 4],
+        clip: Rect,
     },
+    /// A region drawn by the application, identified by the `user_id` given to `Context::custom_draw`.
+    Custom {
+        id: u32,
+        rect: Rect,
+        clip: Rect,
+    },
+}
+
+impl Command {
+    pub fn clip(&self) -> Rect {
+        match *self {
+            Self::Rect { clip, .. }
+            | Self::Text { clip, .. }
+            | Self::Icon { clip, .. }
+            | Self::Image { clip, .. }
+            | Self::Line { clip, .. }
+            | Self::Polyline { clip, .. }
+            | Self::Triangle { clip, .. }
+            | Self::RoundedRect { clip, .. }
+            | Self::Gradient { clip, .. }
+            | Self::Custom { clip, .. } => clip,
+        }
+    }
+
+    /// Converts the command from logical to physical pixels. Polyline points are scaled when pushed.
+    fn scaled(self, s: f32) -> Self {
+        let len = |n: i32| if n > 0 { scale_len(n, s).max(1) } else { 0 };
+        match self {
+            Self::Rect { rect, color, clip } => Self::Rect {
+                rect: rect.scale(s),
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Text { font, pos, color, str_start, str_len, clip } => Self::Text {
+                font,
+                pos: pos.scale(s),
+                color,
+                str_start,
+                str_len,
+                clip: clip.scale(s),
+            },
+            Self::Icon { rect, id, color, clip } => Self::Icon {
+                rect: rect.scale(s),
+                id,
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Image { texture, rect, uv, color, clip } => Self::Image {
+                texture,
+                rect: rect.scale(s),
+                uv,
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Line { p0, p1, thickness, color, clip } => Self::Line {
+                p0: p0.scale(s),
+                p1: p1.scale(s),
+                thickness: len(thickness),
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Polyline { points_start, points_len, thickness, color, clip } => Self::Polyline {
+                points_start,
+                points_len,
+                thickness: len(thickness),
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Triangle { points, color, clip } => Self::Triangle {
+                points: points.map(|p| p.scale(s)),
+                color,
+                clip: clip.scale(s),
+            },
+            Self::RoundedRect { rect, radius, border, color, clip } => Self::RoundedRect {
+                rect: rect.scale(s),
+                radius: len(radius),
+                border: len(border),
+                color,
+                clip: clip.scale(s),
+            },
+            Self::Gradient { rect, radius, colors, clip } => Self::Gradient {
+                rect: rect.scale(s),
+                radius: len(radius),
+                colors,
+                clip: clip.scale(s),
+            },
+            Self::Custom { id, rect, clip } => Self::Custom {
+                id,
+                rect: rect.scale(s),
+                clip: clip.scale(s),
+            },
+        }
+    }
+}
+
+/// A `Command` with its text and points resolved, borrowed from the storage that owns them.
+#[derive(Copy, Clone)]
+pub enum DrawCommand<'a> {
+    Rect {
+        rect: Rect,
+        color: Color,
+        clip: Rect,
+    },
+    Text {
+        font: FontId,
+        text: &'a str,
+        pos: Vec2,
+        color: Color,
+        clip: Rect,
+    },
+    Icon {
+        rect: Rect,
+        id: Icon,
+        color: Color,
+        clip: Rect,
+    },
+    Image {
+        texture: TextureId,
+        rect: Rect,
+        uv: UvRect,
+        color: Color,
+        clip: Rect,
+    },
+    Line {
+        p0: Vec2,
+        p1: Vec2,
+        thickness: i32,
+        color: Color,
+        clip: Rect,
+    },
+    Polyline {
+        points: &'a [Vec2],
+        thickness: i32,
+        color: Color,
+        clip: Rect,
+    },
+    Triangle {
+        points: [Vec2; 3],
+        color: Color,
+        clip: Rect,
+    },
+    RoundedRect {
+        rect: Rect,
+        radius: i32,
+        border: i32,
+        color: Color,
+        clip: Rect,
+    },
+    Gradient {
+        rect: Rect,
+        radius: i32,
+        colors: [Color; 4],
+        clip: Rect,
+    },
+    Custom {
+        id: u32,
+        rect: Rect,
+        clip: Rect,
+    },
+}
+
+impl<'a> DrawCommand<'a> {
+    /// Resolves the offsets of `cmd` into `text` and `points`.
+    pub fn resolve(cmd: &Command, text: &'a str, points: &'a [Vec2]) -> Self {
+        match *cmd {
+            Command::Rect { rect, color, clip } => Self::Rect { rect, color, clip },
+            Command::Text { font, pos, color, str_start, str_len, clip } => Self::Text {
+                font,
+                text: &text[str_start..str_start + str_len],
+                pos,
+                color,
+                clip,
+            },
+            Command::Icon { rect, id, color, clip } => Self::Icon { rect, id, color, clip },
+            Command::Image { texture, rect, uv, color, clip } => Self::Image {
+                texture,
+                rect,
+                uv,
+                color,
+                clip,
+            },
+            Command::Line { p0, p1, thickness, color, clip } => Self::Line {
+                p0,
+                p1,
+                thickness,
+                color,
+                clip,
+            },
+            Command::Polyline { points_start, points_len, thickness, color, clip } => Self::Polyline {
+                points: &points[points_start..points_start + points_len],
+                thickness,
+                color,
+                clip,
+            },
+            Command::Triangle { points, color, clip } => Self::Triangle { points, color, clip },
+            Command::RoundedRect { rect, radius, border, color, clip } => Self::RoundedRect {
+                rect,
+                radius,
+                border,
+                color,
+                clip,
+            },
+            Command::Gradient { rect, radius, colors, clip } => Self::Gradient { rect, radius, colors, clip },
+            Command::Custom { id, rect, clip } => Self::Custom { id, rect, clip },
+        }
+    }
+
+    pub fn clip(&self) -> Rect {
+        match *self {
+            Self::Rect { clip, .. }
+            | Self::Text { clip, .. }
+            | Self::Icon { clip, .. }
+            | Self::Image { clip, .. }
+            | Self::Line { clip, .. }
+            | Self::Polyline { clip, .. }
+            | Self::Triangle { clip, .. }
+            | Self::RoundedRect { clip, .. }
+            | Self::Gradient { clip, .. }
+            | Self::Custom { clip, .. } => clip,
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
 pub struct FontId(pub usize);
 
+/// Handle of an application texture, resolved by the backend.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TextureId(pub usize);
+
 #[derive(Copy, Clone)]
 pub struct Style {
     pub font: FontId,
@@ -173,7 +535,14 @@ pub struct Style {
     pub title_height: i32,
     pub scrollbar_size: i32,
     pub thumb_size: i32,
-    pub colors: [Color; 14],
+    pub corner_radius: i32,
+    pub border_width: i32,
+    pub shadow_offset: Vec2,
+    /// Width in pixels over which window shadows fade out, drawn as that many layered rects.
+    pub shadow_blur: i32,
+    pub colors: [Color; 16],
+    /// Optional bottom color per `ControlColor`, turning frames of that color into a vertical gradient.
+    pub gradients: [Option<Color>; 16],
 }
 
 impl Default for Style {
@@ -187,6 +556,10 @@ impl Default for Style {
             title_height: 24,
             scrollbar_size: 12,
             thumb_size: 8,
+            corner_radius: 0,
+            border_width: 1,
+            shadow_offset: Vec2 { x: 3, y: 4 },
+            shadow_blur: 6,
             colors: [
                 Color::rgba(230, 230, 230, 255),
                 Color::rgba(25, 25, 25, 255),
@@ -202,19 +575,25 @@ impl Default for Style {
                 Color::rgba(40, 40, 40, 255),
                 Color::rgba(43, 43, 43, 255),
                 Color::rgba(30, 30, 30, 255),
+                Color::rgba(0, 0, 0, 96),
+                Color::rgba(120, 150, 210, 255),
             ],
+            gradients: [None; 16],
         }
     }
 }
 
 impl Context {
-    pub fn new(char_width: fn(FontId, char) -> usize, font_height: fn(FontId) -> usize) -> Self {
+    pub fn new(font: impl Font + 'static) -> Self {
         Self {
-            char_width,
-            font_height,
+            fonts: Box::new(font),
             style: Style::default(),
+            scale: 1.0,
             hover: None,
             focus: None,
+            focus_visible: false,
+            focus_order: Vec::new(),
+            key_nav: false,
             last_id: None,
             last_rect: Rect::ZERO,
             last_zindex: 0,
@@ -225,13 +604,20 @@ impl Context {
             scroll_target: None,
             number_edit_buf: String::new(),
             number_edit: None,
+            text_edit: None,
+            text_cursor: 0,
             command_list: vec![],
+            overlay_list: vec![],
+            in_overlay: false,
+            overlay_rects: Vec::new(),
+            overlay_hover: false,
             root_list: vec![],
             container_stack: vec![],
             clip_stack: vec![],
             id_stack: vec![],
             layout_stack: vec![],
             text_stack: String::new(),
+            point_stack: Vec::new(),
             container_pool: Pool::default(),
             containers: [Container::default(); 48],
             treenode_pool: Pool::default(),
@@ -241,31 +627,64 @@ impl Context {
             scroll_delta: Vec2::default(),
             mouse_down: MouseButton::empty(),
             mouse_pressed: MouseButton::empty(),
-            key_down: KeyMode::empty(),
-            key_pressed: KeyMode::empty(),
+            mouse_press_pos: vec2(0, 0),
+            key_down: Keys::empty(),
+            key_pressed: Keys::empty(),
+            modifiers: Modifiers::empty(),
+            nav_pressed: NavInput::empty(),
+            key_activated: Vec::new(),
             input_text: String::new(),
+            event_queue: VecDeque::new(),
+            event_time: 0,
+            input_events: Vec::new(),
         }
     }
 
     pub(crate) fn draw_frame(&mut self, rect: Rect, colorid: ControlColor) {
-        self.draw_rect(rect, self.style.colors[colorid as usize]);
+        let radius = self.style.corner_radius;
+        let color = self.style.colors[colorid as usize];
+        match self.style.gradients[colorid as usize] {
+            Some(bottom) => self.draw_gradient(rect, radius, [color, color, bottom, bottom]),
+            None => self.draw_rounded_rect(rect, radius, color),
+        }
         if colorid == ControlColor::ScrollBase || colorid == ControlColor::ScrollThumb || colorid == ControlColor::TitleBG {
             return;
         }
-        if self.style.colors[ControlColor::Border as usize].a != 0 {
-            self.draw_box(rect.expand(1), self.style.colors[ControlColor::Border as usize]);
+        let width = self.style.border_width;
+        if width > 0 && self.style.colors[ControlColor::Border as usize].a != 0 {
+            let color = self.style.colors[ControlColor::Border as usize];
+            self.draw_rounded_box(rect.expand(width), if radius > 0 { radius + width } else { 0 }, width, color);
         }
     }
 
     pub fn frame(&mut self, f: impl FnOnce(&mut Self)) {
-        self.root_list.clear();
+        self.apply_events();
+        // focus moved by keys is kept only if the control is still updated this frame
+        self.updated_focus = false;
         self.text_stack.clear();
+        self.point_stack.clear();
+        self.focus_order.clear();
         self.scroll_target = None;
-        self.hover_root = self.next_hover_root;
+        if !self.mouse_pressed.is_none() {
+            self.focus_visible = false;
+        }
+        // a press goes to the topmost root under it in the last frame, even when the mouse moved there in this one
+        self.hover_root = if self.mouse_pressed.is_none() {
+            self.next_hover_root
+        } else {
+            self.root_list.iter().rev().copied().find(|&cnt| self.containers[cnt].rect.overlaps(self.mouse_press_pos))
+        };
+        self.overlay_hover = self.overlay_rects.iter().any(|r| r.overlaps(self.hover_pos()));
+        if self.overlay_hover {
+            self.hover_root = None;
+        }
+        self.overlay_rects.clear();
         self.next_hover_root = None;
+        self.root_list.clear();
         self.mouse_delta.x = self.mouse_pos.x - self.last_mouse_pos.x;
         self.mouse_delta.y = self.mouse_pos.y - self.last_mouse_pos.y;
         self.command_list.clear();
+        self.overlay_list.clear();
         self.frame += 1;
 
         f(self);
@@ -274,23 +693,27 @@ impl Context {
         assert_eq!(self.clip_stack.len(), 0);
         assert_eq!(self.id_stack.len(), 0);
         assert_eq!(self.layout_stack.len(), 0);
-        if self.scroll_target.is_some() {
-            self.containers[self.scroll_target.unwrap()].scroll.x += self.scroll_delta.x;
-            self.containers[self.scroll_target.unwrap()].scroll.y += self.scroll_delta.y;
+        if let Some(scroll_target) = self.scroll_target {
+            self.containers[scroll_target].scroll.x += self.scroll_delta.x;
+            self.containers[scroll_target].scroll.y += self.scroll_delta.y;
         }
         if !self.updated_focus {
             self.focus = None;
         }
         self.updated_focus = false;
         if !self.mouse_pressed.is_none()
+            && !self.overlay_hover
             && self.next_hover_root.is_some()
             && self.containers[self.next_hover_root.unwrap()].zindex < self.last_zindex
             && self.containers[self.next_hover_root.unwrap()].zindex >= 0
         {
             self.bring_to_front(self.next_hover_root.unwrap());
         }
-        self.key_pressed = KeyMode::empty();
+        self.key_pressed = Keys::empty();
+        self.nav_pressed = NavInput::empty();
+        self.key_activated.clear();
         self.input_text.clear();
+        self.input_events.clear();
         self.mouse_pressed = MouseButton::empty();
         self.scroll_delta = vec2(0, 0);
         self.last_mouse_pos = self.mouse_pos;
@@ -303,6 +726,65 @@ impl Context {
         self.updated_focus = true;
     }
 
+    /// The controls focus can move between: those of the root holding focus, else of the hover root, else of the
+    /// topmost root with any.
+    fn nav_candidates(&self) -> Vec<(Id, Rect)> {
+        let root = match self.focus_order.iter().find(|e| Some(e.0) == self.focus) {
+            Some(e) => e.2,
+            None => self.hover_root.or_else(|| self.focus_order.iter().filter_map(|e| e.2).max_by_key(|&r| self.containers[r].zindex)),
+        };
+        self.focus_order.iter().filter(|e| e.2 == root).map(|e| (e.0, e.1)).collect()
+    }
+
+    /// Moves focus `step` controls along the focus order of the root holding it, or of the hover root, wrapping around.
+    fn move_focus(&mut self, step: isize) {
+        let ids = self.nav_candidates();
+        if ids.is_empty() {
+            return;
+        }
+        let next = match ids.iter().position(|e| Some(e.0) == self.focus) {
+            Some(i) => (i as isize + step).rem_euclid(ids.len() as isize) as usize,
+            None if step < 0 => ids.len() - 1,
+            None => 0,
+        };
+        self.set_focus(Some(ids[next].0));
+        self.focus_visible = true;
+    }
+
+    /// Moves focus to the nearest control whose center lies past the focused one's edge in direction `dir`,
+    /// preferring controls in line with it. Focuses the first control if none has focus.
+    fn move_focus_toward(&mut self, dir: Vec2) {
+        let controls = self.nav_candidates();
+        let Some(&(_, from)) = controls.iter().find(|e| Some(e.0) == self.focus) else {
+            if let Some(&(id, _)) = controls.first() {
+                self.set_focus(Some(id));
+                self.focus_visible = true;
+            }
+            return;
+        };
+        let center = |r: Rect| vec2(r.x + r.w / 2, r.y + r.h / 2);
+        let (c, half) = (center(from), vec2(from.w / 2, from.h / 2));
+        let nearest = controls
+            .iter()
+            .filter_map(|&(id, r)| {
+                let d = vec2(center(r).x - c.x, center(r).y - c.y);
+                let (along, across) = (d.x * dir.x + d.y * dir.y, (d.x * dir.y - d.y * dir.x).abs());
+                (along > half.x * dir.x.abs() + half.y * dir.y.abs()).then_some((along + 2 * across, id))
+            })
+            .min_by_key(|&(dist, _)| dist);
+        if let Some((_, id)) = nearest {
+            self.set_focus(Some(id));
+            self.focus_visible = true;
+        }
+    }
+
+    /// Whether `NavInput::CANCEL` was input this frame, for the application to close a menu or go back.
+    pub fn nav_cancelled(&self) -> bool { self.nav_pressed.is_cancel() }
+
+    /// Whether the control `id` was clicked this frame, or activated with Return, Space or `NavInput::ACTIVATE` while it
+    /// had keyboard focus.
+    pub(crate) fn activated(&self, id: Id) -> bool { (self.focus == Some(id) && self.mouse_pressed.is_left()) || self.key_activated.contains(&id) }
+
     pub fn get_id_u32(&mut self, orig_id: u32) -> Id {
         let mut res = self.id_stack.last().copied().unwrap_or(Id::START);
         res.hash(orig_id);
@@ -326,6 +808,13 @@ impl Context {
         res
     }
 
+    pub fn get_id_from_bytes(&mut self, bytes: &[u8]) -> Id {
+        let mut res = self.id_stack.last().copied().unwrap_or(Id::START);
+        res.hash(bytes);
+        self.last_id = Some(res);
+        res
+    }
+
     pub fn push_id_from_ptr<T>(&mut self, orig_id: &T) {
         let id = self.get_id_from_ptr(orig_id);
         self.id_stack.push(id);
@@ -412,10 +901,23 @@ impl Context {
         self.containers[cnt].zindex = self.last_zindex;
     }
 
-    fn push_command(&mut self, cmd: Command) -> (&mut Command, usize) {
-        self.command_list.push(cmd);
-        let idx = self.command_list.len() - 1;
-        (self.command_list.last_mut().unwrap(), idx)
+    fn push_command(&mut self, cmd: Command) {
+        let cmd = if self.scale != 1.0 { cmd.scaled(self.scale) } else { cmd };
+        if self.in_overlay {
+            self.overlay_list.push(cmd);
+        } else {
+            self.command_list.push(cmd);
+        }
+    }
+
+    /// Runs `f` with its draw commands routed to the overlay layer, which renders above all windows
+    /// and is not clipped by the current container. Controls updated in `f` take input over every window.
+    pub fn overlay(&mut self, f: impl FnOnce(&mut Self)) {
+        let prev = std::mem::replace(&mut self.in_overlay, true);
+        self.clip_stack.push(Rect::UNCLIPPED);
+        f(self);
+        self.clip_stack.pop();
+        self.in_overlay = prev;
     }
 
     pub fn push_text(&mut self, str: &str) -> usize {
@@ -424,75 +926,182 @@ impl Context {
         str_start
     }
 
+    pub fn push_points(&mut self, points: &[Vec2]) -> usize {
+        let points_start = self.point_stack.len();
+        self.point_stack.extend(points.iter().map(|p| p.scale(self.scale)));
+        points_start
+    }
+
     pub fn commands(&self) -> impl Iterator<Item = &Command> + '_ {
-        self.root_list.iter().flat_map(|&idx| {
-            let container = &self.containers[idx];
-            let head = container.head_idx.unwrap();
-            let tail = container.tail_idx.unwrap();
-            self.command_list[head..tail].iter()
-        })
+        self.root_list
+            .iter()
+            .flat_map(|&idx| {
+                let container = &self.containers[idx];
+                let head = container.head_idx.unwrap();
+                let tail = container.tail_idx.unwrap();
+                self.command_list[head..tail].iter()
+            })
+            .chain(&self.overlay_list)
     }
 
-    fn jump(&mut self) -> usize { self.command_list.len() }
+    /// Iterates the frame's commands in render order with their text and points resolved.
+    pub fn draw_commands(&self) -> impl Iterator<Item = DrawCommand<'_>> + '_ {
+        self.commands().map(|cmd| DrawCommand::resolve(cmd, &self.text_stack, &self.point_stack))
+    }
 
-    pub fn set_clip(&mut self, rect: Rect) { self.push_command(Command::Clip { rect }); }
+    fn jump(&mut self) -> usize { self.command_list.len() }
 
     pub fn draw_rect(&mut self, mut rect: Rect, color: Color) {
         rect = rect.intersect(self.get_clip_rect());
         if rect.w > 0 && rect.h > 0 {
-            self.push_command(Command::Rect { rect, color });
+            self.push_command(Command::Rect {
+                rect,
+                color,
+                clip: Rect::UNCLIPPED,
+            });
+        }
+    }
+
+    /// Outlines `r` using the style's corner radius and border width.
+    pub fn draw_box(&mut self, r: Rect, color: Color) { self.draw_rounded_box(r, self.style.corner_radius, self.style.border_width, color) }
+
+    pub fn draw_rounded_rect(&mut self, rect: Rect, radius: i32, color: Color) {
+        let radius = radius.min(rect.w / 2).min(rect.h / 2);
+        if radius <= 0 {
+            self.draw_rect(rect, color);
+        } else {
+            self.push_clipped_command(rect, |clip| Command::RoundedRect {
+                rect,
+                radius,
+                border: 0,
+                color,
+                clip,
+            });
+        }
+    }
+
+    /// Draws the style's drop shadow for `rect` as concentric translucent rects fading out over `shadow_blur` pixels.
+    pub fn draw_shadow(&mut self, rect: Rect) {
+        let color = self.style.colors[ControlColor::Shadow as usize];
+        let blur = self.style.shadow_blur.max(0);
+        let layer = color.fade(color.a as f32 / 255.0 / (blur + 1) as f32);
+        let r = Rect {
+            x: rect.x + self.style.shadow_offset.x,
+            y: rect.y + self.style.shadow_offset.y,
+            ..rect
+        };
+        for i in (0..=blur).rev() {
+            self.draw_rounded_rect(r.expand(i), self.style.corner_radius + i, layer);
         }
     }
 
-    pub fn draw_box(&mut self, r: Rect, color: Color) {
-        self.draw_rect(rect(r.x + 1, r.y, r.w - 2, 1), color);
-        self.draw_rect(rect(r.x + 1, r.y + r.h - 1, r.w - 2, 1), color);
-        self.draw_rect(rect(r.x, r.y, 1, r.h), color);
-        self.draw_rect(rect(r.x + r.w - 1, r.y, 1, r.h), color);
+    /// Fills `rect` interpolating `colors` given for its top-left, top-right, bottom-right and bottom-left corners.
+    pub fn draw_gradient(&mut self, rect: Rect, radius: i32, colors: [Color; 4]) {
+        let radius = radius.min(rect.w / 2).min(rect.h / 2).max(0);
+        self.push_clipped_command(rect, |clip| Command::Gradient { rect, radius, colors, clip });
+    }
+
+    /// Outlines the inside of `r` with a `width` pixels thick border whose outer corners have the given radius.
+    pub fn draw_rounded_box(&mut self, r: Rect, radius: i32, width: i32, color: Color) {
+        let radius = radius.min(r.w / 2).min(r.h / 2);
+        let width = width.min(r.w / 2).min(r.h / 2);
+        if width <= 0 {
+            return;
+        }
+        if radius > 0 {
+            self.push_clipped_command(r, |clip| Command::RoundedRect {
+                rect: r,
+                radius,
+                border: width,
+                color,
+                clip,
+            });
+            return;
+        }
+        self.draw_rect(rect(r.x + width, r.y, r.w - 2 * width, width), color);
+        self.draw_rect(rect(r.x + width, r.y + r.h - width, r.w - 2 * width, width), color);
+        self.draw_rect(rect(r.x, r.y, width, r.h), color);
+        self.draw_rect(rect(r.x + r.w - width, r.y, width, r.h), color);
     }
 
     pub fn draw_text(&mut self, font: FontId, str: &str, pos: Vec2, color: Color) {
         let rect: Rect = rect(pos.x, pos.y, self.get_text_width(font, str), self.get_text_height(font, str));
-        let clipped = self.check_clip(rect);
-        match clipped {
-            Clip::All => return,
-            Clip::Part => {
-                let clip = self.get_clip_rect();
-                self.set_clip(clip)
-            }
-            _ => (),
+        if self.check_clip(rect) == Clip::All {
+            return;
         }
-
         let str_start = self.push_text(str);
-        self.push_command(Command::Text {
+        self.push_clipped_command(rect, |clip| Command::Text {
             str_start,
             str_len: str.len(),
             pos,
             color,
             font,
+            clip,
         });
-        if clipped != Clip::None {
-            self.set_clip(Rect::UNCLIPPED);
-        }
     }
 
-    pub fn draw_icon(&mut self, id: Icon, rect: Rect, color: Color) {
-        let clipped = self.check_clip(rect);
-        match clipped {
-            Clip::All => return,
-            Clip::Part => {
-                let clip = self.get_clip_rect();
-                self.set_clip(clip)
-            }
-            _ => (),
+    pub fn draw_custom(&mut self, id: u32, rect: Rect) { self.push_clipped_command(rect, |clip| Command::Custom { id, rect, clip }); }
+
+    pub fn draw_icon(&mut self, id: Icon, rect: Rect, color: Color) { self.push_clipped_command(rect, |clip| Command::Icon { id, rect, color, clip }); }
+
+    pub fn draw_image(&mut self, texture: TextureId, rect: Rect, uv: UvRect, color: Color) {
+        self.push_clipped_command(rect, |clip| Command::Image {
+            texture,
+            rect,
+            uv,
+            color,
+            clip,
+        });
+    }
+
+    pub fn draw_line(&mut self, p0: Vec2, p1: Vec2, thickness: i32, color: Color) {
+        let bounds = bounding_rect(&[p0, p1]).expand((thickness + 1) / 2);
+        self.push_clipped_command(bounds, |clip| Command::Line {
+            p0,
+            p1,
+            thickness,
+            color,
+            clip,
+        });
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Vec2], thickness: i32, color: Color) {
+        if points.len() < 2 {
+            return;
         }
-        self.push_command(Command::Icon { id, rect, color });
-        if clipped != Clip::None {
-            self.set_clip(Rect::UNCLIPPED);
+        let bounds = bounding_rect(points).expand((thickness + 1) / 2);
+        if self.check_clip(bounds) == Clip::All {
+            return;
         }
+        let points_start = self.push_points(points);
+        self.push_clipped_command(bounds, |clip| Command::Polyline {
+            points_start,
+            points_len: points.len(),
+            thickness,
+            color,
+            clip,
+        });
+    }
+
+    pub fn draw_triangle(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, color: Color) {
+        let points = [p0, p1, p2];
+        self.push_clipped_command(bounding_rect(&points), |clip| Command::Triangle { points, color, clip });
+    }
+
+    /// Emits the command covering `bounds` built by `cmd` from its effective clip rect, unless it is fully clipped.
+    fn push_clipped_command(&mut self, bounds: Rect, cmd: impl FnOnce(Rect) -> Command) {
+        let clip = match self.check_clip(bounds) {
+            Clip::All => return,
+            Clip::Part => self.get_clip_rect(),
+            Clip::None => Rect::UNCLIPPED,
+        };
+        self.push_command(cmd(clip));
     }
 
     fn in_hover_root(&mut self) -> bool {
+        if self.in_overlay {
+            return true;
+        }
         match self.hover_root {
             Some(hover_root) => {
                 let len = self.container_stack.len();
@@ -510,6 +1119,9 @@ impl Context {
         }
     }
 
+    /// The innermost root container on the container stack.
+    fn current_root(&self) -> Option<usize> { self.container_stack.iter().rev().copied().find(|&c| self.containers[c].head_idx.is_some()) }
+
     pub fn draw_control_frame(&mut self, id: Id, rect: Rect, mut colorid: ControlColor, opt: WidgetOption) {
         if opt.has_no_frame() {
             return;
@@ -540,61 +1152,91 @@ impl Context {
         self.pop_clip_rect();
     }
 
-    pub fn mouse_over(&mut self, rect: Rect) -> bool { rect.overlaps(self.mouse_pos) && self.get_clip_rect().overlaps(self.mouse_pos) && self.in_hover_root() }
+    pub fn mouse_over(&mut self, rect: Rect) -> bool { self.over_at(rect, self.mouse_pos) }
+
+    /// Whether a mouse button was pressed this frame over `rect`, where the mouse was at the time.
+    fn pressed_over(&mut self, rect: Rect) -> bool { !self.mouse_pressed.is_none() && self.over_at(rect, self.mouse_press_pos) }
+
+    fn over_at(&mut self, rect: Rect, pos: Vec2) -> bool { rect.overlaps(pos) && self.get_clip_rect().overlaps(pos) && self.in_hover_root() }
+
+    /// The position hover roots are picked at: where a button was pressed this frame, else the mouse position.
+    pub(crate) fn hover_pos(&self) -> Vec2 {
+        if self.mouse_pressed.is_none() {
+            self.mouse_pos
+        } else {
+            self.mouse_press_pos
+        }
+    }
 
     pub fn update_control(&mut self, id: Id, rect: Rect, opt: WidgetOption) {
         let mouseover = self.mouse_over(rect);
+        let pressed = self.pressed_over(rect);
         if self.focus == Some(id) {
             self.updated_focus = true;
         }
         if opt.is_not_interactive() {
             return;
         }
+        if self.in_overlay {
+            let clip = self.get_clip_rect();
+            self.overlay_rects.push(rect.intersect(clip));
+        }
+        if !opt.has_no_nav() {
+            self.focus_order.push((id, rect, self.current_root()));
+        }
+        if self.focus == Some(id) && self.focus_visible {
+            let radius = self.style.corner_radius;
+            let color = self.style.colors[ControlColor::FocusRing as usize];
+            self.draw_rounded_box(rect.expand(2), if radius > 0 { radius + 2 } else { 0 }, 1, color);
+        }
         if mouseover && self.mouse_down.is_none() {
             self.hover = Some(id);
         }
         if self.focus == Some(id) {
-            if !self.mouse_pressed.is_none() && !mouseover {
+            if !self.mouse_pressed.is_none() && !pressed {
                 self.set_focus(None);
             }
-            if self.mouse_down.is_none() && !opt.is_holding_focus() {
+            if self.mouse_down.is_none() && !opt.is_holding_focus() && !self.focus_visible {
                 self.set_focus(None);
             }
         }
-        if self.hover == Some(id) {
-            if !self.mouse_pressed.is_none() {
-                self.set_focus(Some(id));
-            } else if !mouseover {
-                self.hover = None;
-            }
+        if pressed {
+            self.hover = Some(id);
+            self.set_focus(Some(id));
+        } else if self.hover == Some(id) && !mouseover {
+            self.hover = None;
         }
     }
 
     pub fn get_text_width(&self, font: FontId, text: &str) -> i32 {
         let mut res = 0;
         let mut acc = 0;
+        let mut prev = None;
         for c in text.chars() {
             if c == '\n' {
-                res = usize::max(res, acc);
+                res = i32::max(res, acc);
                 acc = 0;
+                prev = None;
+            }
+            if let Some(prev) = prev {
+                acc += self.fonts.kerning(font, prev, c);
             }
-            acc += (self.char_width)(font, c);
+            acc += self.fonts.char_width(font, c);
+            prev = Some(c);
         }
-        res = usize::max(res, acc);
-        res as i32
+        i32::max(res, acc)
     }
 
     pub fn get_text_height(&self, font: FontId, text: &str) -> i32 {
-        let font_height = (self.font_height)(font);
-        let lc = text.lines().count();
-        (lc * font_height) as i32
+        let lc = text.lines().count() as i32;
+        lc * self.fonts.font_height(font) + (lc - 1).max(0) * self.fonts.line_gap(font)
     }
 
     pub fn text(&mut self, text: &str) {
         let font = self.style.font;
         let color = self.style.colors[ControlColor::Text as usize];
         self.layout_begin_column();
-        let h = (self.font_height)(font) as i32;
+        let h = self.fonts.line_height(font);
         self.layout_row(&[-1], h);
         let mut r = self.layout_next();
         for line in text.lines() {
@@ -621,13 +1263,18 @@ impl Context {
         self.draw_control_text(text, layout, ControlColor::Text, WidgetOption::empty());
     }
 
+    pub fn image(&mut self, texture: TextureId, uv: UvRect, color: Color) {
+        let layout = self.layout_next();
+        self.draw_image(texture, layout, uv, color);
+    }
+
     pub fn checkbox(&mut self, label: &str, state: &mut bool) -> ResourceState {
         let mut res = ResourceState::empty();
         let id: Id = self.get_id_from_ptr(state);
         let mut r: Rect = self.layout_next();
         let box_0: Rect = rect(r.x, r.y, r.h, r.h);
         self.update_control(id, r, WidgetOption::empty());
-        if self.mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.activated(id) {
             res |= ResourceState::CHANGE;
             *state = !(*state);
         }
@@ -643,37 +1290,74 @@ impl Context {
     pub fn textbox_raw(&mut self, buf: &mut String, id: Id, r: Rect, opt: WidgetOption) -> ResourceState {
         let mut res = ResourceState::empty();
         self.update_control(id, r, opt | WidgetOption::HOLD_FOCUS);
-        if self.focus == Some(id) {
-            let mut len = buf.len();
-
-            if !self.input_text.is_empty() {
-                buf.push_str(&self.input_text);
-                len += self.input_text.len();
-                res |= ResourceState::CHANGE
+        // keys typed while the textbox had focus still apply if a click later in the frame took it away
+        let typed = self.input_events.iter().any(|e| e.1 == Some(id));
+        if self.focus == Some(id) || typed {
+            if self.text_edit != Some(id) {
+                self.text_edit = Some(id);
+                self.text_cursor = buf.len();
+            }
+            let mut cursor = self.text_cursor.min(buf.len());
+            while !buf.is_char_boundary(cursor) {
+                cursor -= 1;
             }
 
-            if self.key_pressed.is_backspace() && len > 0 {
-                // skip utf-8 continuation bytes
-                buf.pop();
-                res |= ResourceState::CHANGE
+            // typing and editing keys apply in the order they happened, up to Return or Escape
+            let mut done = false;
+            for (timed, _) in self.input_events.iter().filter(|e| e.1 == Some(id)) {
+                match timed.event {
+                    InputEvent::Text(ref text) => {
+                        buf.insert_str(cursor, text);
+                        cursor += text.len();
+                        res |= ResourceState::CHANGE
+                    }
+                    InputEvent::KeyDown(Key::Backspace) if cursor > 0 => {
+                        let start = prev_char(buf, cursor);
+                        buf.replace_range(start..cursor, "");
+                        cursor = start;
+                        res |= ResourceState::CHANGE
+                    }
+                    InputEvent::KeyDown(Key::Delete) if cursor < buf.len() => {
+                        buf.replace_range(cursor..next_char(buf, cursor), "");
+                        res |= ResourceState::CHANGE
+                    }
+                    InputEvent::KeyDown(Key::Left) => cursor = prev_char(buf, cursor),
+                    InputEvent::KeyDown(Key::Right) => cursor = next_char(buf, cursor),
+                    InputEvent::KeyDown(Key::Home) => cursor = 0,
+                    InputEvent::KeyDown(Key::End) => cursor = buf.len(),
+                    InputEvent::KeyDown(Key::Return) => {
+                        res |= ResourceState::SUBMIT;
+                        done = true;
+                        break;
+                    }
+                    InputEvent::KeyDown(Key::Escape) => {
+                        done = true;
+                        break;
+                    }
+                    _ => (),
+                }
             }
-            if self.key_pressed.is_return() {
+            self.text_cursor = cursor;
+
+            if done && self.focus == Some(id) {
                 self.set_focus(None);
-                res |= ResourceState::SUBMIT;
             }
         }
+        if self.focus != Some(id) && self.text_edit == Some(id) {
+            self.text_edit = None;
+        }
         self.draw_control_frame(id, r, ControlColor::Base, opt);
         if self.focus == Some(id) {
             let color = self.style.colors[ControlColor::Text as usize];
             let font = self.style.font;
-            let textw = self.get_text_width(font, buf.as_str());
+            let cursorw = self.get_text_width(font, &buf[..self.text_cursor]);
             let texth = self.get_text_height(font, buf.as_str());
-            let ofx = r.w - self.style.padding - textw - 1;
+            let ofx = r.w - self.style.padding - cursorw - 1;
             let textx = r.x + (if ofx < self.style.padding { ofx } else { self.style.padding });
             let texty = r.y + (r.h - texth) / 2;
             self.push_clip_rect(r);
             self.draw_text(font, buf.as_str(), vec2(textx, texty), color);
-            self.draw_rect(rect(textx + textw, texty, 1, texth), color);
+            self.draw_rect(rect(textx + cursorw, texty, 1, texth), color);
             self.pop_clip_rect();
         } else {
             self.draw_control_text(buf.as_str(), r, ControlColor::Text, opt);
@@ -682,7 +1366,7 @@ impl Context {
     }
 
     fn number_textbox(&mut self, precision: usize, value: &mut f32, r: Rect, id: Id) -> ResourceState {
-        if self.mouse_pressed.is_left() && self.key_down.is_shift() && self.hover == Some(id) {
+        if self.mouse_pressed.is_left() && self.modifiers.is_shift() && self.hover == Some(id) {
             self.number_edit = Some(id);
             self.number_edit_buf.clear();
             let _ = write!(self.number_edit_buf, "{:.*}", precision, value);
@@ -776,7 +1460,7 @@ impl Context {
         let expanded = if opt.is_expanded() { (active == 0) as i32 } else { active };
         let mut r = self.layout_next();
         self.update_control(id, r, WidgetOption::empty());
-        active ^= (self.mouse_pressed.is_left() && self.focus == Some(id)) as i32;
+        active ^= self.activated(id) as i32;
         if let Some(idx) = idx {
             if active != 0 {
                 self.treenode_pool.update(idx, self.frame);
@@ -828,7 +1512,7 @@ impl Context {
             let mut base = body;
             base.x = body.x + body.w;
             base.w = self.style.scrollbar_size;
-            self.update_control(id, base, WidgetOption::empty());
+            self.update_control(id, base, WidgetOption::NO_NAV);
             if self.focus == Some(id) && self.mouse_down.is_left() {
                 self.containers[cnt_id].scroll.y += self.mouse_delta.y * cs.y / base.h;
             }
@@ -855,7 +1539,7 @@ impl Context {
             let mut base_0 = body;
             base_0.y = body.y + body.h;
             base_0.h = self.style.scrollbar_size;
-            self.update_control(id_0, base_0, WidgetOption::empty());
+            self.update_control(id_0, base_0, WidgetOption::NO_NAV);
             if self.focus == Some(id_0) && self.mouse_down.is_left() {
                 self.containers[cnt_id].scroll.x += self.mouse_delta.x * cs.x / base_0.w;
             }
@@ -888,3 +1572,9 @@ impl Context {
         self.containers[cnt_idx].body = body;
     }
 }
+
+/// Byte offset of the char before the one at `i` in `s`.
+fn prev_char(s: &str, i: usize) -> usize { s[..i].char_indices().next_back().map_or(0, |(i, _)| i) }
+
+/// Byte offset of the char after the one at `i` in `s`.
+fn next_char(s: &str, i: usize) -> usize { s[i..].chars().next().map_or(i, |c| i + c.len_utf8()) }
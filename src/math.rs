@@ -6,7 +6,11 @@ pub struct Vec2 {
 
 pub const fn vec2(x: i32, y: i32) -> Vec2 { Vec2 { x, y } }
 
-#[derive(Default, Copy, Clone, PartialEq)]
+impl Vec2 {
+    pub fn scale(&self, s: f32) -> Self { vec2(scale_len(self.x, s), scale_len(self.y, s)) }
+}
+
+#[derive(Default, Copy, Clone, PartialEq, Debug)]
 pub struct Rect {
     pub x: i32,
     pub y: i32,
@@ -20,6 +24,48 @@ impl Rect {
 
 pub const fn rect(x: i32, y: i32, w: i32, h: i32) -> Rect { Rect { x, y, w, h } }
 
+/// Smallest rect containing all `points`, or `Rect::ZERO` if there are none.
+pub fn bounding_rect(points: &[Vec2]) -> Rect {
+    if points.is_empty() {
+        return Rect::ZERO;
+    }
+    let (mut x0, mut y0, mut x1, mut y1) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
+    for p in points {
+        x0 = min(x0, p.x);
+        y0 = min(y0, p.y);
+        x1 = max(x1, p.x);
+        y1 = max(y1, p.y);
+    }
+    rect(x0, y0, x1 - x0, y1 - y0)
+}
+
+/// Corners of the quad covering a `thickness` wide line from `p0` to `p1`, or `None` if the points coincide.
+pub(crate) fn line_quad(p0: Vec2, p1: Vec2, thickness: i32) -> Option<[[f32; 2]; 4]> {
+    let (dx, dy) = ((p1.x - p0.x) as f32, (p1.y - p0.y) as f32);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0.0 {
+        return None;
+    }
+    let (nx, ny) = (-dy / len * thickness as f32 / 2.0, dx / len * thickness as f32 / 2.0);
+    let (x0, y0, x1, y1) = (p0.x as f32, p0.y as f32, p1.x as f32, p1.y as f32);
+    Some([[x0 + nx, y0 + ny], [x1 + nx, y1 + ny], [x1 - nx, y1 - ny], [x0 - nx, y0 - ny]])
+}
+
+/// Normalized texture coordinates of a source region, from `(u0, v0)` to `(u1, v1)`.
+#[derive(Default, Copy, Clone, PartialEq)]
+pub struct UvRect {
+    pub u0: f32,
+    pub v0: f32,
+    pub u1: f32,
+    pub v1: f32,
+}
+
+impl UvRect {
+    pub const FULL: Self = uv_rect(0.0, 0.0, 1.0, 1.0);
+}
+
+pub const fn uv_rect(u0: f32, v0: f32, u1: f32, v1: f32) -> UvRect { UvRect { u0, v0, u1, v1 } }
+
 impl Rect {
     pub const UNCLIPPED: Rect = rect(0, 0, 0x1000000, 0x1000000);
 
@@ -41,8 +87,19 @@ impl Rect {
     }
 
     pub fn overlaps(&self, p: Vec2) -> bool { p.x >= self.x && p.x < self.x + self.w && p.y >= self.y && p.y < self.y + self.h }
+
+    /// Scales the edges of the rect by `s`, so rects sharing an edge still do after scaling. `UNCLIPPED` is kept as is.
+    pub fn scale(&self, s: f32) -> Self {
+        if *self == Self::UNCLIPPED {
+            return *self;
+        }
+        let (x0, y0) = (scale_len(self.x, s), scale_len(self.y, s));
+        rect(x0, y0, scale_len(self.x + self.w, s) - x0, scale_len(self.y + self.h, s) - y0)
+    }
 }
 
+pub(crate) fn scale_len(n: i32, s: f32) -> i32 { (n as f32 * s).round() as i32 }
+
 #[inline]
 pub(crate) const fn min(a: i32, b: i32) -> i32 {
     if a < b {
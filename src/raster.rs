@@ -0,0 +1,243 @@
+use crate::atlas::*;
+use crate::color::gradient_at;
+use crate::*;
+
+/// RGBA8 pixels of an application texture.
+#[derive(Copy, Clone)]
+pub struct Texture<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [u8],
+}
+
+/// CPU renderer drawing the command list into a caller-supplied RGBA8 framebuffer.
+pub struct Rasterizer<'a> {
+    pixels: &'a mut [u8],
+    width: usize,
+    height: usize,
+    clip: Rect,
+    scale: f32,
+    atlas: &'a Atlas,
+    font: Option<&'a dyn Font>,
+    textures: Vec<(TextureId, Texture<'a>)>,
+}
+
+impl<'a> Rasterizer<'a> {
+    pub fn new(pixels: &'a mut [u8], width: usize, height: usize) -> Self {
+        assert_eq!(pixels.len(), width * height * 4);
+        Self {
+            pixels,
+            width,
+            height,
+            clip: rect(0, 0, width as i32, height as i32),
+            scale: 1.0,
+            atlas: Atlas::builtin(),
+            font: None,
+            textures: Vec::new(),
+        }
+    }
+
+    /// Makes `pixels` available to `Command::Image` under `id`, replacing any previous texture. An empty texture
+    /// removes `id`, so images using it are skipped.
+    pub fn set_texture(&mut self, id: TextureId, width: usize, height: usize, pixels: &'a [u8]) {
+        assert_eq!(pixels.len(), width * height * 4);
+        self.textures.retain(|(t, _)| *t != id);
+        if width > 0 && height > 0 {
+            self.textures.push((id, Texture { width, height, pixels }));
+        }
+    }
+
+    /// Draws rects, text and icons from `atlas` instead of the built-in one.
+    pub fn set_atlas(&mut self, atlas: &'a Atlas) { self.atlas = atlas; }
+
+    /// Kerns text with `font` instead of the atlas, see `Atlas::layout_text`.
+    pub fn set_font(&mut self, font: &'a dyn Font) { self.font = Some(font); }
+
+    /// Draws the frame's commands. Clipping is reset afterwards.
+    pub fn render(&mut self, ctx: &Context) {
+        self.set_scale(ctx.scale);
+        self.render_commands(ctx.commands(), &ctx.text_stack, &ctx.point_stack)
+    }
+
+    /// Draws `commands`, whose text and polyline points index into `text` and `points`. Clipping is reset afterwards.
+    pub fn render_commands<'c>(&mut self, commands: impl IntoIterator<Item = &'c Command>, text: &str, points: &[Vec2]) {
+        crate::backend::render_commands(self, commands.into_iter().map(|cmd| DrawCommand::resolve(cmd, text, points)));
+        self.set_clip(Rect::UNCLIPPED);
+    }
+
+    pub fn width(&self) -> usize { self.width }
+
+    pub fn height(&self) -> usize { self.height }
+
+    pub fn clear(&mut self, color: Color) {
+        for px in self.pixels.chunks_exact_mut(4) {
+            px.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    /// Fills the pixels whose centers fall inside the triangle, using the top-left rule on shared edges.
+    fn fill_triangle(&mut self, mut t: [[f32; 2]; 3], color: Color) {
+        let edge = |a: [f32; 2], b: [f32; 2], x: f32, y: f32| (b[0] - a[0]) * (y - a[1]) - (b[1] - a[1]) * (x - a[0]);
+        let area = edge(t[0], t[1], t[2][0], t[2][1]);
+        if area == 0.0 {
+            return;
+        }
+        if area < 0.0 {
+            t.swap(1, 2);
+        }
+
+        let x0 = t.iter().map(|p| p[0]).fold(f32::MAX, f32::min).floor() as i32;
+        let y0 = t.iter().map(|p| p[1]).fold(f32::MAX, f32::min).floor() as i32;
+        let x1 = t.iter().map(|p| p[0]).fold(f32::MIN, f32::max).ceil() as i32;
+        let y1 = t.iter().map(|p| p[1]).fold(f32::MIN, f32::max).ceil() as i32;
+        let r = rect(x0, y0, x1 - x0, y1 - y0).intersect(self.clip);
+        let edges = [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])];
+        for y in r.y..r.y + r.h {
+            for x in r.x..r.x + r.w {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let inside = edges.iter().all(|&(a, b)| {
+                    let e = edge(a, b, px, py);
+                    let top_left = b[1] < a[1] || (b[1] == a[1] && b[0] > a[0]);
+                    e > 0.0 || (e == 0.0 && top_left)
+                });
+                if inside {
+                    self.blend(x, y, color, 255);
+                }
+            }
+        }
+    }
+
+    /// Blits the `src` region of the atlas into `dst`, tinted by `color`.
+    pub fn push_rect(&mut self, dst: Rect, src: Rect, color: Color) {
+        if dst.w <= 0 || dst.h <= 0 || src.w <= 0 || src.h <= 0 {
+            return;
+        }
+        let r = dst.intersect(self.clip);
+        for y in r.y..r.y + r.h {
+            let sy = src.y + (y - dst.y) * src.h / dst.h;
+            for x in r.x..r.x + r.w {
+                let sx = src.x + (x - dst.x) * src.w / dst.w;
+                let texel = self.atlas.texel(sx, sy);
+                let tint = |c: u8, t: u8| (c as u32 * t as u32 / 255) as u8;
+                self.blend(x, y, Color::rgba(tint(color.r, texel.r), tint(color.g, texel.g), tint(color.b, texel.b), color.a), texel.a);
+            }
+        }
+    }
+
+    fn blend(&mut self, x: i32, y: i32, color: Color, coverage: u8) {
+        let a = color.a as u32 * coverage as u32 / 255;
+        if a == 0 {
+            return;
+        }
+        let idx = (y as usize * self.width + x as usize) * 4;
+        let px = &mut self.pixels[idx..idx + 4];
+        let inv = 255 - a;
+        px[0] = ((color.r as u32 * a + px[0] as u32 * inv + 127) / 255) as u8;
+        px[1] = ((color.g as u32 * a + px[1] as u32 * inv + 127) / 255) as u8;
+        px[2] = ((color.b as u32 * a + px[2] as u32 * inv + 127) / 255) as u8;
+        px[3] = (a + (px[3] as u32 * inv + 127) / 255) as u8;
+    }
+}
+
+impl Backend for Rasterizer<'_> {
+    /// Glyphs and icons are scaled up from the atlas.
+    fn set_scale(&mut self, scale: f32) { self.scale = scale; }
+
+    fn set_clip(&mut self, r: Rect) { self.clip = r.intersect(rect(0, 0, self.width as i32, self.height as i32)); }
+
+    fn draw_rect(&mut self, r: Rect, color: Color) {
+        let r = r.intersect(self.clip);
+        for y in r.y..r.y + r.h {
+            for x in r.x..r.x + r.w {
+                self.blend(x, y, color, 255);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, font: FontId, text: &str, pos: Vec2, color: Color) {
+        let atlas = self.atlas;
+        for (dst, src) in atlas.layout_text(self.font.unwrap_or(atlas), font, text, pos, self.scale) {
+            self.push_rect(dst, src, color);
+        }
+    }
+
+    fn draw_icon(&mut self, id: Icon, r: Rect, color: Color) {
+        let (dst, src) = self.atlas.layout_icon(id, r, self.scale);
+        self.push_rect(dst, src, color);
+    }
+
+    /// Draws the `uv` region of a texture registered with `set_texture`, tinted by `color`. Unknown textures are skipped.
+    fn draw_image(&mut self, id: TextureId, dst: Rect, uv: UvRect, color: Color) {
+        let Some(&(_, tex)) = self.textures.iter().find(|(t, _)| *t == id) else {
+            return;
+        };
+        if dst.w <= 0 || dst.h <= 0 {
+            return;
+        }
+        let r = dst.intersect(self.clip);
+        for y in r.y..r.y + r.h {
+            let v = uv.v0 + (uv.v1 - uv.v0) * ((y - dst.y) as f32 + 0.5) / dst.h as f32;
+            let ty = ((v * tex.height as f32) as usize).min(tex.height - 1);
+            for x in r.x..r.x + r.w {
+                let u = uv.u0 + (uv.u1 - uv.u0) * ((x - dst.x) as f32 + 0.5) / dst.w as f32;
+                let tx = ((u * tex.width as f32) as usize).min(tex.width - 1);
+                let px = &tex.pixels[(ty * tex.width + tx) * 4..][..4];
+                let texel = Color::rgba(
+                    (px[0] as u32 * color.r as u32 / 255) as u8,
+                    (px[1] as u32 * color.g as u32 / 255) as u8,
+                    (px[2] as u32 * color.b as u32 / 255) as u8,
+                    (px[3] as u32 * color.a as u32 / 255) as u8,
+                );
+                self.blend(x, y, texel, 255);
+            }
+        }
+    }
+
+    fn draw_line(&mut self, p0: Vec2, p1: Vec2, thickness: i32, color: Color) {
+        let Some(quad) = line_quad(p0, p1, thickness) else {
+            return;
+        };
+        self.fill_triangle([quad[0], quad[1], quad[2]], color);
+        self.fill_triangle([quad[0], quad[2], quad[3]], color);
+    }
+
+    fn draw_triangle(&mut self, points: [Vec2; 3], color: Color) { self.fill_triangle(points.map(|p| [p.x as f32, p.y as f32]), color) }
+
+    /// Fills `r` with rounded corners, or only a `border` pixels thick outline of it when `border` is non-zero.
+    fn draw_rounded_rect(&mut self, r: Rect, radius: i32, border: i32, color: Color) {
+        let inner = r.expand(-border);
+        let has_inner = border > 0 && inner.w > 0 && inner.h > 0;
+        let clipped = r.intersect(self.clip);
+        for y in clipped.y..clipped.y + clipped.h {
+            for x in clipped.x..clipped.x + clipped.w {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let mut coverage = rounded_coverage(r, radius, px, py);
+                if has_inner {
+                    coverage *= 1.0 - rounded_coverage(inner, (radius - border).max(0), px, py);
+                }
+                self.blend(x, y, color, (coverage * 255.0 + 0.5) as u8);
+            }
+        }
+    }
+
+    fn draw_gradient(&mut self, r: Rect, radius: i32, colors: [Color; 4]) {
+        let clipped = r.intersect(self.clip);
+        for y in clipped.y..clipped.y + clipped.h {
+            for x in clipped.x..clipped.x + clipped.w {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let coverage = rounded_coverage(r, radius, px, py);
+                self.blend(x, y, gradient_at(&colors, r, px, py), (coverage * 255.0 + 0.5) as u8);
+            }
+        }
+    }
+}
+
+/// Fraction of the pixel centered at `px,py` covered by `r` with rounded corners, from its signed distance.
+fn rounded_coverage(r: Rect, radius: i32, px: f32, py: f32) -> f32 {
+    let (hw, hh) = (r.w as f32 / 2.0, r.h as f32 / 2.0);
+    let radius = radius as f32;
+    let qx = (px - r.x as f32 - hw).abs() - (hw - radius);
+    let qy = (py - r.y as f32 - hh).abs() - (hh - radius);
+    let dist = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt() + qx.max(qy).min(0.0) - radius;
+    (0.5 - dist).clamp(0.0, 1.0)
+}
@@ -0,0 +1,140 @@
+use crate::raster::Rasterizer;
+use crate::*;
+use std::path::Path;
+
+/// An RGBA8 image of a rendered frame, used for golden-image comparisons.
+#[derive(Clone)]
+pub struct Snapshot {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height * 4],
+        }
+    }
+
+    /// Runs `f` through `Context::frame` and rasterizes the result over `clear`.
+    pub fn capture(ctx: &mut Context, width: usize, height: usize, clear: Color, f: impl FnOnce(&mut Context)) -> Self {
+        ctx.frame(f);
+        let mut res = Self::new(width, height);
+        let mut rd = Rasterizer::new(&mut res.pixels, width, height);
+        rd.clear(clear);
+        rd.render(ctx);
+        res
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let idx = (y * self.width + x) * 4;
+        Color::rgba(self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2], self.pixels[idx + 3])
+    }
+
+    /// Encodes the image as a binary (P6) PPM, dropping the alpha channel.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut res = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for px in self.pixels.chunks_exact(4) {
+            res.extend_from_slice(&px[..3]);
+        }
+        res
+    }
+
+    pub fn from_ppm(data: &[u8]) -> Option<Self> {
+        let mut pos = 0;
+        let mut fields = [0; 3];
+        if !data.starts_with(b"P6") {
+            return None;
+        }
+        pos += 2;
+        for field in fields.iter_mut() {
+            loop {
+                match data.get(pos)? {
+                    b'#' => {
+                        while *data.get(pos)? != b'\n' {
+                            pos += 1;
+                        }
+                    }
+                    c if c.is_ascii_whitespace() => pos += 1,
+                    _ => break,
+                }
+            }
+            let start = pos;
+            while data.get(pos)?.is_ascii_digit() {
+                pos += 1;
+            }
+            *field = std::str::from_utf8(&data[start..pos]).ok()?.parse::<usize>().ok()?;
+        }
+        let [width, height, max] = fields;
+        if max != 255 || !data.get(pos)?.is_ascii_whitespace() {
+            return None;
+        }
+        let len = width.checked_mul(height)?.checked_mul(3)?;
+        let rgb = data.get(pos + 1..pos + 1 + len)?;
+        let mut res = Self::new(width, height);
+        for (dst, src) in res.pixels.chunks_exact_mut(4).zip(rgb.chunks_exact(3)) {
+            dst.copy_from_slice(&[src[0], src[1], src[2], 255]);
+        }
+        Some(res)
+    }
+
+    /// Compares the RGB channels against `other`. Returns the number of pixels differing by more
+    /// than `tolerance` on any channel along with an image highlighting them in red, or `None` on a match.
+    pub fn diff(&self, other: &Self, tolerance: u8) -> Option<(usize, Snapshot)> {
+        assert!(self.width == other.width && self.height == other.height);
+        let mut res = Self::new(self.width, self.height);
+        let mut count = 0;
+        for ((dst, a), b) in res.pixels.chunks_exact_mut(4).zip(self.pixels.chunks_exact(4)).zip(other.pixels.chunks_exact(4)) {
+            if (0..3).any(|i| a[i].abs_diff(b[i]) > tolerance) {
+                dst.copy_from_slice(&[255, 0, 0, 255]);
+                count += 1;
+            } else {
+                let l = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 6) as u8;
+                dst.copy_from_slice(&[l, l, l, 255]);
+            }
+        }
+        if count > 0 {
+            Some((count, res))
+        } else {
+            None
+        }
+    }
+}
+
+/// Compares `actual` against the PPM reference at `path`, panicking on a mismatch.
+///
+/// The reference is written instead when `MICROUI_BLESS` is set; a missing reference is a failure otherwise. On
+/// failure `<name>.actual.ppm` and `<name>.diff.ppm` are written next to the reference.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &Snapshot, tolerance: u8) {
+    let path = path.as_ref();
+    if std::env::var_os("MICROUI_BLESS").is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(path, actual.to_ppm()).unwrap();
+        return;
+    }
+
+    let Ok(data) = std::fs::read(path) else {
+        // the directory may be missing too, and the panic below is what matters
+        let _ = std::fs::write(path.with_extension("actual.ppm"), actual.to_ppm());
+        panic!("snapshot {} is missing, run with MICROUI_BLESS=1 to create it", path.display());
+    };
+    let expected = Snapshot::from_ppm(&data).unwrap_or_else(|| panic!("{}: not a binary PPM image", path.display()));
+    let mismatch = if expected.width != actual.width || expected.height != actual.height {
+        Some(format!("size {}x{} differs from reference {}x{}", actual.width, actual.height, expected.width, expected.height))
+    } else if let Some((count, diff)) = actual.diff(&expected, tolerance) {
+        std::fs::write(path.with_extension("diff.ppm"), diff.to_ppm()).unwrap();
+        Some(format!("{} pixels differ by more than {}", count, tolerance))
+    } else {
+        None
+    };
+
+    if let Some(msg) = mismatch {
+        std::fs::write(path.with_extension("actual.ppm"), actual.to_ppm()).unwrap();
+        panic!("snapshot {} mismatch: {}", path.display(), msg);
+    }
+}
@@ -18,7 +18,7 @@ impl SimpleHash for u32 {
     }
 }
 
-impl<'a> SimpleHash for &'a [u8] {
+impl SimpleHash for &[u8] {
     fn hash(self, id: &mut Id) {
         for byte in self {
             (*byte as u32).hash(id)
@@ -26,6 +26,6 @@ impl<'a> SimpleHash for &'a [u8] {
     }
 }
 
-impl<'a> SimpleHash for &'a str {
+impl SimpleHash for &str {
     fn hash(self, id: &mut Id) { self.as_bytes().hash(id) }
 }
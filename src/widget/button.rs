@@ -3,6 +3,7 @@ use crate::*;
 enum ButtonLabel<'t> {
     Text(&'t str),
     Icon(Icon),
+    Image(TextureId, UvRect),
 }
 
 pub struct Button<'t> {
@@ -23,18 +24,33 @@ impl<'t> Button<'t> {
 impl Context {
     pub fn button<'t>(&self, label: &'t str) -> Button<'t> { Button::new(ButtonLabel::Text(label)) }
 
-    pub fn button_icon(&self, icon: Icon) -> Button<'_> { Button::new(ButtonLabel::Icon(icon)) }
+    pub fn button_icon(&self, icon: Icon) -> Button<'static> { Button::new(ButtonLabel::Icon(icon)) }
+
+    pub fn button_image(&self, texture: TextureId, uv: UvRect) -> Button<'static> { Button::new(ButtonLabel::Image(texture, uv)) }
 
     fn button_ex(&mut self, label: ButtonLabel, opt: WidgetOption) -> ResourceState {
         let mut res = ResourceState::empty();
         let id = match label {
             ButtonLabel::Text(text) => self.get_id_from_str(text),
-            ButtonLabel::Icon(icon) => self.get_id_u32(icon as _),
+            // icon and image ids start with 0xff, which never occurs in utf-8 text, followed by a byte for the kind
+            ButtonLabel::Icon(icon) => {
+                let mut bytes = vec![0xff, b'i'];
+                bytes.extend((icon.index() as u32).to_le_bytes());
+                self.get_id_from_bytes(&bytes)
+            }
+            ButtonLabel::Image(texture, uv) => {
+                let mut bytes = vec![0xff, b'm'];
+                bytes.extend((texture.0 as u64).to_le_bytes());
+                for v in [uv.u0, uv.v0, uv.u1, uv.v1] {
+                    bytes.extend(v.to_bits().to_le_bytes());
+                }
+                self.get_id_from_bytes(&bytes)
+            }
         };
 
         let r = self.layout_next();
         self.update_control(id, r, opt);
-        if self.mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.activated(id) {
             res |= ResourceState::SUBMIT;
         }
         self.draw_control_frame(id, r, ControlColor::Button, opt);
@@ -42,6 +58,7 @@ impl Context {
         match label {
             ButtonLabel::Text(text) => self.draw_control_text(text, r, ControlColor::Text, opt),
             ButtonLabel::Icon(icon) => self.draw_icon(icon, r, self.style.colors[ControlColor::Text as usize]),
+            ButtonLabel::Image(texture, uv) => self.draw_image(texture, r.expand(-self.style.padding), uv, Color::WHITE),
         };
 
         res
@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Placement and interaction state of an application-drawn region reserved with `Context::custom_draw`.
+///
+/// Rects are in physical pixels, like those of the `Command::Custom` emitted for the region.
+#[derive(Copy, Clone)]
+pub struct CustomDraw {
+    pub rect: Rect,
+    /// The rect the application must scissor its drawing to.
+    pub clip: Rect,
+    pub hovered: bool,
+    pub focused: bool,
+}
+
+impl Context {
+    /// Reserves the next layout rect for content the application renders itself, emitting a
+    /// `Command::Custom` tagged with `user_id` at this point of the container's draw order.
+    pub fn custom_draw(&mut self, user_id: u32) -> CustomDraw { self.custom_draw_ex(user_id, WidgetOption::empty()) }
+
+    pub fn custom_draw_ex(&mut self, user_id: u32, opt: WidgetOption) -> CustomDraw {
+        let id = self.get_id_u32(user_id);
+        let r = self.layout_next();
+        self.update_control(id, r, opt);
+        self.draw_custom(user_id, r);
+        CustomDraw {
+            rect: r.scale(self.scale),
+            clip: r.intersect(self.get_clip_rect()).scale(self.scale),
+            hovered: self.hover == Some(id),
+            focused: self.focus == Some(id),
+        }
+    }
+}
@@ -1,9 +1,11 @@
 mod button;
+mod custom;
 mod header;
 mod panel;
 mod tree;
 mod window;
 
+pub use custom::*;
 pub use window::*;
 
 bitflags::bitflags! {
@@ -22,6 +24,8 @@ bitflags::bitflags! {
         const POPUP        = 1 << 10;
         const CLOSED       = 1 << 11;
         const EXPANDED     = 1 << 12;
+        const SHADOW       = 1 << 13;
+        const NO_NAV       = 1 << 14;
     }
 }
 
@@ -29,6 +33,8 @@ impl WidgetOption {
     pub const fn is_expanded(&self) -> bool { self.intersects(WidgetOption::EXPANDED) }
     pub const fn is_closed(&self) -> bool { self.intersects(WidgetOption::CLOSED) }
     pub const fn is_popup(&self) -> bool { self.intersects(WidgetOption::POPUP) }
+    pub const fn has_shadow(&self) -> bool { self.intersects(WidgetOption::SHADOW) }
+    pub const fn has_no_nav(&self) -> bool { self.intersects(WidgetOption::NO_NAV) }
     pub const fn is_auto_sizing(&self) -> bool { self.intersects(WidgetOption::AUTO_SIZE) }
     pub const fn is_holding_focus(&self) -> bool { self.intersects(WidgetOption::HOLD_FOCUS) }
     pub const fn has_no_title(&self) -> bool { self.intersects(WidgetOption::NO_TITLE) }
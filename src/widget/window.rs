@@ -45,7 +45,13 @@ pub struct Popup<'t>(Window<'t>);
 impl<'t> Popup<'t> {
     pub fn new(title: &'t str) -> Self {
         Self(Window::new(title).options(
-            WidgetOption::POPUP | WidgetOption::AUTO_SIZE | WidgetOption::NO_RESIZE | WidgetOption::NO_SCROLL | WidgetOption::NO_TITLE | WidgetOption::CLOSED,
+            WidgetOption::POPUP
+                | WidgetOption::AUTO_SIZE
+                | WidgetOption::NO_RESIZE
+                | WidgetOption::NO_SCROLL
+                | WidgetOption::NO_TITLE
+                | WidgetOption::CLOSED
+                | WidgetOption::SHADOW,
         ))
     }
 
@@ -80,6 +86,9 @@ impl Context {
         self.begin_root_container(cnt_id.unwrap());
         let mut body = self.containers[cnt_id.unwrap()].rect;
         r = body;
+        if opt.has_shadow() {
+            self.draw_shadow(r);
+        }
         if !opt.has_no_frame() {
             self.draw_frame(r, ControlColor::WindowBG);
         }
@@ -89,7 +98,7 @@ impl Context {
             self.draw_frame(tr, ControlColor::TitleBG);
 
             let id = self.get_id_from_str("!title");
-            self.update_control(id, tr, opt);
+            self.update_control(id, tr, opt | WidgetOption::NO_NAV);
             self.draw_control_text(title, tr, ControlColor::TitleText, opt);
             if Some(id) == self.focus && self.mouse_down.is_left() {
                 self.containers[cnt_id.unwrap()].rect.x += self.mouse_delta.x;
@@ -101,10 +110,9 @@ impl Context {
             if !opt.has_no_close() {
                 let id = self.get_id_from_str("!close");
                 let r = rect(tr.x + tr.w - tr.h, tr.y, tr.h, tr.h);
-                tr.w -= r.w;
                 self.draw_icon(Icon::Close, r, self.style.colors[ControlColor::TitleText as usize]);
-                self.update_control(id, r, opt);
-                if self.mouse_pressed.is_left() && Some(id) == self.focus {
+                self.update_control(id, r, opt | WidgetOption::NO_NAV);
+                if self.activated(id) {
                     self.containers[cnt_id.unwrap()].open = false;
                 }
             }
@@ -114,7 +122,7 @@ impl Context {
             let sz = self.style.title_height;
             let id_2 = self.get_id_from_str("!resize");
             let r_0 = rect(r.x + r.w - sz, r.y + r.h - sz, sz, sz);
-            self.update_control(id_2, r_0, opt);
+            self.update_control(id_2, r_0, opt | WidgetOption::NO_NAV);
             if Some(id_2) == self.focus && self.mouse_down.is_left() {
                 self.containers[cnt_id.unwrap()].rect.w = if 96 > self.containers[cnt_id.unwrap()].rect.w + self.mouse_delta.x {
                     96
@@ -150,7 +158,7 @@ impl Context {
 
         self.root_list.push(cnt);
         self.containers[cnt].head_idx = Some(self.jump());
-        if self.containers[cnt].rect.overlaps(self.mouse_pos)
+        if self.containers[cnt].rect.overlaps(self.hover_pos())
             && (self.next_hover_root.is_none() || self.containers[cnt].zindex > self.containers[self.next_hover_root.unwrap()].zindex)
         {
             self.next_hover_root = Some(cnt);
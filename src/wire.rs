@@ -0,0 +1,398 @@
+use crate::*;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+pub const MAGIC: [u8; 4] = *b"MUI\0";
+/// Version of the recording format, bumped only when a released format changes.
+pub const VERSION: u16 = 1;
+
+const TAG_RECT: u8 = 1;
+const TAG_TEXT: u8 = 2;
+const TAG_ICON: u8 = 3;
+const TAG_IMAGE: u8 = 4;
+const TAG_LINE: u8 = 5;
+const TAG_POLYLINE: u8 = 6;
+const TAG_TRIANGLE: u8 = 7;
+const TAG_ROUNDED_RECT: u8 = 8;
+const TAG_GRADIENT: u8 = 9;
+const TAG_CUSTOM: u8 = 10;
+
+#[derive(Debug)]
+pub enum WireError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    InvalidTag(u8),
+    InvalidIcon(u32),
+    InvalidUtf8,
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::BadMagic => write!(f, "not a microui recording"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported recording version {}", v),
+            Self::InvalidTag(tag) => write!(f, "invalid command tag {}", tag),
+            Self::InvalidIcon(id) => write!(f, "invalid icon id {}", id),
+            Self::InvalidUtf8 => write!(f, "text is not valid utf-8"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<io::Error> for WireError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+/// A self-contained copy of one frame's commands in render order, owning the text and points they reference.
+#[derive(Clone)]
+pub struct Frame {
+    pub commands: Vec<Command>,
+    pub text: String,
+    pub points: Vec<Vec2>,
+    /// The `Context::scale` the frame was captured at.
+    pub scale: f32,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            text: String::new(),
+            points: Vec::new(),
+            scale: 1.0,
+        }
+    }
+}
+
+impl Frame {
+    pub fn capture(ctx: &Context) -> Self {
+        let mut res = Self { scale: ctx.scale, ..Self::default() };
+        for &cmd in ctx.commands() {
+            match cmd {
+                Command::Text { font, pos, color, str_start, str_len, clip } => {
+                    res.push_text(font, pos, color, &ctx.text_stack[str_start..str_start + str_len], clip)
+                }
+                Command::Polyline { points_start, points_len, thickness, color, clip } => {
+                    res.push_polyline(&ctx.point_stack[points_start..points_start + points_len], thickness, color, clip)
+                }
+                cmd => res.commands.push(cmd),
+            }
+        }
+        res
+    }
+
+    pub fn draw_commands(&self) -> impl Iterator<Item = DrawCommand<'_>> + '_ {
+        self.commands.iter().map(|cmd| DrawCommand::resolve(cmd, &self.text, &self.points))
+    }
+
+    pub fn render(&self, backend: &mut impl Backend) {
+        backend.set_scale(self.scale);
+        crate::backend::render_commands(backend, self.draw_commands())
+    }
+
+    pub fn dump(&self) -> String { crate::dump::dump(self.draw_commands()) }
+
+    fn push_text(&mut self, font: FontId, pos: Vec2, color: Color, str: &str, clip: Rect) {
+        let str_start = self.text.len();
+        self.text.push_str(str);
+        self.commands.push(Command::Text {
+            font,
+            pos,
+            color,
+            str_start,
+            str_len: str.len(),
+            clip,
+        });
+    }
+
+    fn push_polyline(&mut self, points: &[Vec2], thickness: i32, color: Color, clip: Rect) {
+        let points_start = self.points.len();
+        self.points.extend_from_slice(points);
+        self.commands.push(Command::Polyline {
+            points_start,
+            points_len: points.len(),
+            thickness,
+            color,
+            clip,
+        });
+    }
+
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&(self.commands.len() as u32).to_le_bytes())?;
+        w.write_all(&self.scale.to_le_bytes())?;
+        for cmd in self.draw_commands() {
+            match cmd {
+                DrawCommand::Rect { rect, color, clip } => {
+                    w.write_all(&[TAG_RECT])?;
+                    write_rect(w, clip)?;
+                    write_rect(w, rect)?;
+                    write_color(w, color)?;
+                }
+                DrawCommand::Text { font, text, pos, color, clip } => {
+                    w.write_all(&[TAG_TEXT])?;
+                    write_rect(w, clip)?;
+                    write_u32(w, font.0 as u32)?;
+                    write_i32(w, pos.x)?;
+                    write_i32(w, pos.y)?;
+                    write_color(w, color)?;
+                    write_u32(w, text.len() as u32)?;
+                    w.write_all(text.as_bytes())?;
+                }
+                DrawCommand::Icon { rect, id, color, clip } => {
+                    w.write_all(&[TAG_ICON])?;
+                    write_rect(w, clip)?;
+                    write_u32(w, id.index() as u32)?;
+                    write_rect(w, rect)?;
+                    write_color(w, color)?;
+                }
+                DrawCommand::Image { texture, rect, uv, color, clip } => {
+                    w.write_all(&[TAG_IMAGE])?;
+                    write_rect(w, clip)?;
+                    write_u32(w, texture.0 as u32)?;
+                    write_rect(w, rect)?;
+                    for v in [uv.u0, uv.v0, uv.u1, uv.v1] {
+                        w.write_all(&v.to_le_bytes())?;
+                    }
+                    write_color(w, color)?;
+                }
+                DrawCommand::Line { p0, p1, thickness, color, clip } => {
+                    w.write_all(&[TAG_LINE])?;
+                    write_rect(w, clip)?;
+                    write_point(w, p0)?;
+                    write_point(w, p1)?;
+                    write_i32(w, thickness)?;
+                    write_color(w, color)?;
+                }
+                DrawCommand::Polyline { points, thickness, color, clip } => {
+                    w.write_all(&[TAG_POLYLINE])?;
+                    write_rect(w, clip)?;
+                    write_u32(w, points.len() as u32)?;
+                    for &p in points {
+                        write_point(w, p)?;
+                    }
+                    write_i32(w, thickness)?;
+                    write_color(w, color)?;
+                }
+                DrawCommand::Triangle { points, color, clip } => {
+                    w.write_all(&[TAG_TRIANGLE])?;
+                    write_rect(w, clip)?;
+                    for p in points {
+                        write_point(w, p)?;
+                    }
+                    write_color(w, color)?;
+                }
+                DrawCommand::RoundedRect { rect, radius, border, color, clip } => {
+                    w.write_all(&[TAG_ROUNDED_RECT])?;
+                    write_rect(w, clip)?;
+                    write_rect(w, rect)?;
+                    write_i32(w, radius)?;
+                    write_i32(w, border)?;
+                    write_color(w, color)?;
+                }
+                DrawCommand::Gradient { rect, radius, colors, clip } => {
+                    w.write_all(&[TAG_GRADIENT])?;
+                    write_rect(w, clip)?;
+                    write_rect(w, rect)?;
+                    write_i32(w, radius)?;
+                    for color in colors {
+                        write_color(w, color)?;
+                    }
+                }
+                DrawCommand::Custom { id, rect, clip } => {
+                    w.write_all(&[TAG_CUSTOM])?;
+                    write_rect(w, clip)?;
+                    write_u32(w, id)?;
+                    write_rect(w, rect)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next frame, or `None` if the stream ends cleanly before it.
+    pub fn read(r: &mut impl Read) -> Result<Option<Self>, WireError> {
+        let mut count = [0; 4];
+        match r.read(&mut count[..1])? {
+            0 => return Ok(None),
+            _ => r.read_exact(&mut count[1..])?,
+        }
+
+        let mut res = Self { scale: read_f32(r)?, ..Self::default() };
+        for _ in 0..u32::from_le_bytes(count) {
+            let tag = read_u8(r)?;
+            let clip = read_rect(r)?;
+            match tag {
+                TAG_RECT => res.commands.push(Command::Rect {
+                    rect: read_rect(r)?,
+                    color: read_color(r)?,
+                    clip,
+                }),
+                TAG_TEXT => {
+                    let font = FontId(read_u32(r)? as usize);
+                    let pos = vec2(read_i32(r)?, read_i32(r)?);
+                    let color = read_color(r)?;
+                    // grow with the data actually read so a corrupt length cannot request a huge buffer
+                    let len = read_u32(r)? as usize;
+                    let mut bytes = Vec::new();
+                    r.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+                    if bytes.len() != len {
+                        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                    }
+                    let str = String::from_utf8(bytes).map_err(|_| WireError::InvalidUtf8)?;
+                    res.push_text(font, pos, color, &str, clip);
+                }
+                TAG_ICON => {
+                    let id = read_u32(r)?;
+                    let id = Icon::from_index(id as usize).ok_or(WireError::InvalidIcon(id))?;
+                    res.commands.push(Command::Icon {
+                        id,
+                        rect: read_rect(r)?,
+                        color: read_color(r)?,
+                        clip,
+                    });
+                }
+                TAG_IMAGE => res.commands.push(Command::Image {
+                    texture: TextureId(read_u32(r)? as usize),
+                    rect: read_rect(r)?,
+                    uv: uv_rect(read_f32(r)?, read_f32(r)?, read_f32(r)?, read_f32(r)?),
+                    color: read_color(r)?,
+                    clip,
+                }),
+                TAG_LINE => res.commands.push(Command::Line {
+                    p0: read_point(r)?,
+                    p1: read_point(r)?,
+                    thickness: read_i32(r)?,
+                    color: read_color(r)?,
+                    clip,
+                }),
+                TAG_POLYLINE => {
+                    let mut points = Vec::new();
+                    for _ in 0..read_u32(r)? {
+                        points.push(read_point(r)?);
+                    }
+                    let thickness = read_i32(r)?;
+                    let color = read_color(r)?;
+                    res.push_polyline(&points, thickness, color, clip);
+                }
+                TAG_TRIANGLE => res.commands.push(Command::Triangle {
+                    points: [read_point(r)?, read_point(r)?, read_point(r)?],
+                    color: read_color(r)?,
+                    clip,
+                }),
+                TAG_ROUNDED_RECT => res.commands.push(Command::RoundedRect {
+                    rect: read_rect(r)?,
+                    radius: read_i32(r)?,
+                    border: read_i32(r)?,
+                    color: read_color(r)?,
+                    clip,
+                }),
+                TAG_GRADIENT => res.commands.push(Command::Gradient {
+                    rect: read_rect(r)?,
+                    radius: read_i32(r)?,
+                    colors: [read_color(r)?, read_color(r)?, read_color(r)?, read_color(r)?],
+                    clip,
+                }),
+                TAG_CUSTOM => res.commands.push(Command::Custom {
+                    id: read_u32(r)?,
+                    rect: read_rect(r)?,
+                    clip,
+                }),
+                tag => return Err(WireError::InvalidTag(tag)),
+            }
+        }
+        Ok(Some(res))
+    }
+}
+
+/// Writes the recording header followed by one encoded frame per `record` call.
+pub struct Recorder<W: Write> {
+    w: W,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(mut w: W) -> io::Result<Self> {
+        w.write_all(&MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self { w })
+    }
+
+    pub fn record(&mut self, ctx: &Context) -> io::Result<()> { self.write_frame(&Frame::capture(ctx)) }
+
+    pub fn write_frame(&mut self, frame: &Frame) -> io::Result<()> { frame.write(&mut self.w) }
+
+    pub fn into_inner(self) -> W { self.w }
+}
+
+/// Iterates the frames of a recording written by `Recorder`.
+pub struct Replay<R: Read> {
+    r: R,
+}
+
+impl<R: Read> Replay<R> {
+    pub fn new(mut r: R) -> Result<Self, WireError> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(WireError::BadMagic);
+        }
+        let mut version = [0; 2];
+        r.read_exact(&mut version)?;
+        match u16::from_le_bytes(version) {
+            VERSION => Ok(Self { r }),
+            v => Err(WireError::UnsupportedVersion(v)),
+        }
+    }
+}
+
+impl<R: Read> Iterator for Replay<R> {
+    type Item = Result<Frame, WireError>;
+
+    fn next(&mut self) -> Option<Self::Item> { Frame::read(&mut self.r).transpose() }
+}
+
+pub(crate) fn write_u32(w: &mut impl Write, v: u32) -> io::Result<()> { w.write_all(&v.to_le_bytes()) }
+
+pub(crate) fn write_i32(w: &mut impl Write, v: i32) -> io::Result<()> { w.write_all(&v.to_le_bytes()) }
+
+pub(crate) fn write_point(w: &mut impl Write, p: Vec2) -> io::Result<()> {
+    write_i32(w, p.x)?;
+    write_i32(w, p.y)
+}
+
+pub(crate) fn write_rect(w: &mut impl Write, r: Rect) -> io::Result<()> {
+    for v in [r.x, r.y, r.w, r.h] {
+        write_i32(w, v)?;
+    }
+    Ok(())
+}
+
+fn write_color(w: &mut impl Write, c: Color) -> io::Result<()> { w.write_all(&[c.r, c.g, c.b, c.a]) }
+
+pub(crate) fn read_u8(r: &mut impl Read) -> io::Result<u8> {
+    let mut buf = [0; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+pub(crate) fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub(crate) fn read_i32(r: &mut impl Read) -> io::Result<i32> { Ok(read_u32(r)? as i32) }
+
+fn read_f32(r: &mut impl Read) -> io::Result<f32> { Ok(f32::from_bits(read_u32(r)?)) }
+
+pub(crate) fn read_point(r: &mut impl Read) -> io::Result<Vec2> { Ok(vec2(read_i32(r)?, read_i32(r)?)) }
+
+pub(crate) fn read_rect(r: &mut impl Read) -> io::Result<Rect> { Ok(rect(read_i32(r)?, read_i32(r)?, read_i32(r)?, read_i32(r)?)) }
+
+fn read_color(r: &mut impl Read) -> io::Result<Color> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(Color::from(buf))
+}
@@ -0,0 +1,39 @@
+use microui::atlas::*;
+use microui::*;
+
+fn encode(atlas: &Atlas) -> Vec<u8> {
+    let mut res = Vec::new();
+    atlas.write(&mut res).unwrap();
+    res
+}
+
+fn sample() -> Atlas { AtlasBuilder::new().width(128).icon(2, 2, vec![255; 4]).font(BitmapFont::builtin()).build() }
+
+#[test]
+fn write_read_round_trip() {
+    let atlas = sample();
+    let bytes = encode(&atlas);
+    let read = Atlas::read(&mut &bytes[..]).unwrap();
+    assert_eq!((read.width, read.height), (atlas.width, atlas.height));
+    assert_eq!(read.pixels, atlas.pixels);
+    assert_eq!(read.icon(Icon::User(0)), atlas.icon(Icon::User(0)));
+    assert_eq!(read.glyph(FontId(0), 'a').map(|g| g.rect), atlas.glyph(FontId(0), 'a').map(|g| g.rect));
+    assert_eq!(encode(&read), bytes);
+}
+
+#[test]
+fn bad_sizes_and_rects_are_errors() {
+    let atlas = sample();
+    let bytes = encode(&atlas);
+    let white = 4 + 2 + 4 + 4 + 1 + atlas.pixels.len();
+
+    let mut huge = bytes.clone();
+    huge[6..14].copy_from_slice(&[0xff, 0xff, 0, 0, 0xff, 0xff, 0, 0]);
+    assert!(matches!(Atlas::read(&mut &huge[..]), Err(AtlasError::InvalidSize(0xffff, 0xffff))));
+
+    let mut outside = bytes.clone();
+    outside[white..white + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+    assert!(matches!(Atlas::read(&mut &outside[..]), Err(AtlasError::RectOutOfBounds(_))));
+
+    assert!(matches!(Atlas::read(&mut &bytes[..white - 1]), Err(AtlasError::Io(_))));
+}
@@ -0,0 +1,18 @@
+use microui::*;
+
+#[test]
+fn custom_draw_rect_matches_command_at_scale() {
+    let mut ctx = Context::new(AtlasFont);
+    ctx.scale = 2.0;
+    let mut draw = None;
+    ctx.frame(|ui| {
+        ui.window("W").position(10, 10).size(100, 100).show(ui, |ui| draw = Some(ui.custom_draw(7)));
+    });
+    let draw = draw.unwrap();
+    let cmd = ctx.commands().find_map(|cmd| match *cmd {
+        Command::Custom { id: 7, rect, .. } => Some(rect),
+        _ => None,
+    });
+    assert!(cmd == Some(draw.rect));
+    assert!(draw.clip == draw.rect);
+}
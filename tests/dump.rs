@@ -0,0 +1,46 @@
+use microui::*;
+
+#[test]
+fn dump_rect_text_and_clip() {
+    let mut ctx = Context::new(AtlasFont);
+    let opt = WidgetOption::NO_FRAME | WidgetOption::NO_TITLE | WidgetOption::NO_RESIZE;
+    ctx.frame(|ui| {
+        ui.window("W").position(0, 0).size(100, 100).options(opt).show(ui, |ui| {
+            ui.draw_rect(rect(10, 10, 20, 20), Color::rgba(255, 0, 0, 255));
+            ui.push_clip_rect(rect(0, 0, 15, 15));
+            ui.draw_text(FontId(0), "hi", vec2(5, 5), Color::rgba(0, 0, 0, 255));
+            ui.pop_clip_rect();
+        });
+    });
+    assert_eq!(ctx.dump_commands(), "rect 10,10 20x20 #ff0000ff\ntext \"hi\" @ 5,5 #000000ff font 0 clip 0,0 15x15\n");
+}
+
+/// Draws window "Low" with a red rect and a blue overlay rect, and window "High" over it with a green rect.
+fn overlay_frame(ctx: &mut Context) {
+    let opt = WidgetOption::NO_FRAME | WidgetOption::NO_TITLE | WidgetOption::NO_RESIZE;
+    ctx.frame(|ui| {
+        ui.window("Low").position(0, 0).size(50, 50).options(opt).show(ui, |ui| {
+            ui.draw_rect(rect(1, 1, 2, 2), Color::rgba(255, 0, 0, 255));
+            ui.overlay(|ui| ui.draw_rect(rect(25, 25, 2, 2), Color::rgba(0, 0, 255, 255)));
+        });
+        ui.window("High").position(20, 20).size(50, 50).options(opt).show(ui, |ui| {
+            ui.draw_rect(rect(30, 30, 2, 2), Color::rgba(0, 255, 0, 255));
+        });
+    });
+}
+
+#[test]
+fn overlay_follows_every_window() {
+    let mut ctx = Context::new(AtlasFont);
+    overlay_frame(&mut ctx);
+    let (low, high, overlay) = ("rect 1,1 2x2 #ff0000ff\n", "rect 30,30 2x2 #00ff00ff\n", "rect 25,25 2x2 #0000ffff\n");
+    assert_eq!(ctx.dump_commands(), [low, high, overlay].concat());
+
+    // raising the window that emits the overlay keeps the overlay last
+    ctx.input_mousemove(5, 5);
+    ctx.input_mousedown(MouseButton::LEFT);
+    overlay_frame(&mut ctx);
+    ctx.input_mouseup(MouseButton::LEFT);
+    overlay_frame(&mut ctx);
+    assert_eq!(ctx.dump_commands(), [high, low, overlay].concat());
+}
@@ -0,0 +1,188 @@
+use microui::*;
+
+/// Draws buttons "A" and "B" and a textbox holding `text`, returning the buttons clicked and the rect of each control.
+fn ui(ctx: &mut Context, text: &mut String) -> (Vec<&'static str>, Vec<Rect>) {
+    let (mut clicked, mut rects) = (Vec::new(), Vec::new());
+    ctx.frame(|ui| {
+        ui.window("W").position(0, 0).size(200, 200).show(ui, |ui| {
+            for label in ["A", "B"] {
+                ui.button(label).show(ui, |_| clicked.push(label));
+                rects.push(ui.last_rect);
+            }
+            ui.textbox_ex(text, WidgetOption::empty());
+            rects.push(ui.last_rect);
+        });
+    });
+    (clicked, rects)
+}
+
+fn center(r: Rect) -> (i32, i32) { (r.x + r.w / 2, r.y + r.h / 2) }
+
+#[test]
+fn click_within_one_frame_goes_to_control_under_press() {
+    let mut ctx = Context::new(AtlasFont);
+    let mut text = String::new();
+    let (_, rects) = ui(&mut ctx, &mut text);
+
+    // the mouse arrives over B and clicks it before the next frame runs
+    let (x, y) = center(rects[1]);
+    ctx.input_mousemove(x, y);
+    ctx.input_mousedown(MouseButton::LEFT);
+    ctx.input_mouseup(MouseButton::LEFT);
+    let (clicked, _) = ui(&mut ctx, &mut text);
+    assert_eq!(clicked, ["B"]);
+    assert!(ctx.mouse_down.is_left());
+
+    let (clicked, _) = ui(&mut ctx, &mut text);
+    assert!(clicked.is_empty());
+    assert!(ctx.mouse_down.is_none());
+    assert!(!ctx.has_pending_events());
+}
+
+#[test]
+fn keystroke_burst_applies_in_one_frame() {
+    let mut ctx = Context::new(AtlasFont);
+    let mut text = String::new();
+    let (_, rects) = ui(&mut ctx, &mut text);
+    let (x, y) = center(rects[2]);
+    ctx.input_mousemove(x, y);
+    ctx.input_mousedown(MouseButton::LEFT);
+    ui(&mut ctx, &mut text);
+    ctx.input_mouseup(MouseButton::LEFT);
+    ui(&mut ctx, &mut text);
+
+    // typed the way the demo reports it, with the modifiers before every key event
+    for (key, c, mods) in [(Key::H, "H", Modifiers::SHIFT), (Key::I, "i", Modifiers::empty()), (Key::I, "i", Modifiers::empty())] {
+        ctx.input_modifiers(mods);
+        ctx.input_keydown(key);
+        ctx.input_text(c);
+        ctx.input_modifiers(mods);
+        ctx.input_keyup(key);
+    }
+    ctx.input_keydown(Key::Backspace);
+    ctx.input_keyup(Key::Backspace);
+    ctx.input_text("!");
+    ui(&mut ctx, &mut text);
+    assert_eq!(text, "Hi!");
+    assert!(!ctx.has_pending_events());
+    assert!(ctx.key_down.is_empty());
+}
+
+#[test]
+fn focus_keys_apply_in_order_within_one_frame() {
+    let mut ctx = Context::new(AtlasFont);
+    let mut text = String::new();
+    ui(&mut ctx, &mut text);
+
+    // Tab to A, Tab to B, activate B, then Tab on to the textbox and type into it
+    for key in [Key::Tab, Key::Tab, Key::Return, Key::Tab] {
+        ctx.input_keydown(key);
+        ctx.input_keyup(key);
+    }
+    ctx.input_text("x");
+    let (clicked, _) = ui(&mut ctx, &mut text);
+    assert_eq!(clicked, ["B"]);
+    assert_eq!(text, "x");
+    assert!(!ctx.has_pending_events());
+}
+
+#[test]
+fn events_keep_their_timestamps() {
+    let mut ctx = Context::new(AtlasFont);
+    ctx.push_event(InputEvent::MouseMove(5, 5), 100);
+    ctx.push_event(InputEvent::KeyDown(Key::A), 120);
+    ctx.input_text("a");
+    let mut times = Vec::new();
+    ctx.frame(|ui| times.extend(ui.input_events().map(|e| e.time)));
+    assert_eq!(times, [100, 120, 120]);
+}
+
+/// Draws a 2x2 grid of buttons in a titled, closable window, returning the buttons clicked and the id of each.
+fn grid(ctx: &mut Context) -> (Vec<&'static str>, Vec<Option<Id>>) {
+    let (mut clicked, mut ids) = (Vec::new(), Vec::new());
+    ctx.frame(|ui| {
+        ui.window("Grid").position(0, 0).size(200, 200).show(ui, |ui| {
+            ui.layout_row(&[80, 80], 0);
+            for label in ["A", "B", "C", "D"] {
+                ui.button(label).show(ui, |_| clicked.push(label));
+                ids.push(ui.last_id);
+            }
+        });
+    });
+    (clicked, ids)
+}
+
+#[test]
+fn nav_moves_focus_across_grid_and_activates() {
+    let mut ctx = Context::new(AtlasFont);
+    let (_, ids) = grid(&mut ctx);
+    assert!(ids.iter().all(Option::is_some) && ids[0] != ids[1] && ids[2] != ids[3]);
+    for (nav, expected) in [(NavInput::DOWN, 0), (NavInput::RIGHT, 1), (NavInput::DOWN, 3), (NavInput::LEFT, 2)] {
+        ctx.input_nav(nav);
+        let (clicked, _) = grid(&mut ctx);
+        assert!(clicked.is_empty());
+        assert!(ctx.focus == ids[expected]);
+    }
+    ctx.input_nav(NavInput::ACTIVATE);
+    let (clicked, _) = grid(&mut ctx);
+    assert_eq!(clicked, ["C"]);
+    assert!(ctx.focus == ids[2]);
+
+    ctx.input_nav(NavInput::CANCEL);
+    let mut cancelled = false;
+    ctx.frame(|ui| cancelled = ui.nav_cancelled());
+    assert!(cancelled);
+}
+
+#[test]
+fn nav_and_tab_skip_window_controls() {
+    let mut ctx = Context::new(AtlasFont);
+    let (_, ids) = grid(&mut ctx);
+    let mut seen = Vec::new();
+    for _ in 0..5 {
+        ctx.input_keydown(Key::Tab);
+        grid(&mut ctx);
+        seen.push(ctx.focus);
+    }
+    assert!(seen == [ids[0], ids[1], ids[2], ids[3], ids[0]]);
+
+    // nothing lies above the top row but the title bar, whose controls are built with NO_NAV
+    ctx.input_nav(NavInput::UP);
+    grid(&mut ctx);
+    assert!(ctx.focus == ids[0]);
+}
+
+/// Draws window "Low" with button "Over" in the overlay at 30,30, and window "High" above it filled by button "Under".
+fn overlay_button(ctx: &mut Context) -> Vec<&'static str> {
+    let opt = WidgetOption::NO_FRAME | WidgetOption::NO_TITLE | WidgetOption::NO_RESIZE;
+    let mut clicked = Vec::new();
+    ctx.frame(|ui| {
+        ui.window("Low").position(0, 0).size(50, 50).options(opt).show(ui, |ui| {
+            ui.overlay(|ui| {
+                ui.layout_set_next(rect(30, 30, 20, 20), LayoutPosition::Absolute);
+                ui.button("Over").show(ui, |_| clicked.push("Over"));
+            });
+        });
+        ui.window("High").position(20, 20).size(50, 50).options(opt).show(ui, |ui| {
+            ui.layout_row(&[-1], -1);
+            ui.button("Under").show(ui, |_| clicked.push("Under"));
+        });
+    });
+    clicked
+}
+
+#[test]
+fn overlay_controls_take_input_over_windows() {
+    let mut ctx = Context::new(AtlasFont);
+    overlay_button(&mut ctx);
+    ctx.input_mousemove(40, 40);
+    ctx.input_mousedown(MouseButton::LEFT);
+    assert_eq!(overlay_button(&mut ctx), ["Over"]);
+    ctx.input_mouseup(MouseButton::LEFT);
+    overlay_button(&mut ctx);
+
+    // outside the overlay button the window beneath gets the click
+    ctx.input_mousemove(60, 60);
+    ctx.input_mousedown(MouseButton::LEFT);
+    assert_eq!(overlay_button(&mut ctx), ["Under"]);
+}
@@ -0,0 +1,51 @@
+use microui::atlas::*;
+
+fn psf2(count: u32, charsize: u32, height: u32, width: u32, bitmaps: &[u8]) -> Vec<u8> {
+    let mut res = vec![0x72, 0xb5, 0x4a, 0x86];
+    for v in [0, 32, 0, count, charsize, height, width] {
+        res.extend_from_slice(&u32::to_le_bytes(v));
+    }
+    res.extend_from_slice(bitmaps);
+    res
+}
+
+#[test]
+fn psf2_glyphs() {
+    // two 3x2 glyphs, one byte per row
+    let font = BitmapFont::from_psf(&psf2(2, 2, 2, 3, &[0b1010_0000, 0b0100_0000, 0, 0b1110_0000])).unwrap();
+    assert_eq!(font.glyphs.len(), 2);
+    assert_eq!((font.glyphs[0].width, font.glyphs[0].height), (3, 2));
+    assert_eq!(font.glyphs[0].alpha, [255, 0, 255, 0, 255, 0]);
+    assert_eq!(font.glyphs[1].alpha, [0, 0, 0, 255, 255, 255]);
+}
+
+#[test]
+fn psf2_bad_header_sizes_are_errors() {
+    let truncated = |data: &[u8]| matches!(BitmapFont::from_psf(data), Err(FontError::TruncatedPsf));
+    // count * charsize overflows
+    assert!(truncated(&psf2(u32::MAX, u32::MAX, 1, 8, &[0; 8])));
+    // a glyph larger than the data there is for it
+    assert!(truncated(&psf2(1, 1, 1 << 20, 1 << 20, &[0])));
+    // charsize smaller than a glyph's rows
+    assert!(truncated(&psf2(1, 1, 2, 8, &[0; 2])));
+    assert!(truncated(&psf2(4, 2, 2, 8, &[0; 6])));
+    assert!(matches!(BitmapFont::from_psf(b"nope"), Err(FontError::BadPsfMagic)));
+}
+
+#[test]
+fn bdf_bitmap_without_rows_is_an_error() {
+    let src = "STARTFONT 2.1\nFONTBOUNDINGBOX 1 1 0 0\nSTARTCHAR a\nENCODING 97\nBBX 100000 100000 0 0\nBITMAP\nff\n";
+    assert!(matches!(BitmapFont::from_bdf(src), Err(FontError::Bdf(_))));
+}
+
+#[test]
+fn builtin_font_matches_builtin_atlas() {
+    let font = BitmapFont::builtin();
+    let atlas = Atlas::builtin();
+    assert_eq!(font.glyphs.len(), atlas.fonts[0].glyphs.len());
+    assert_eq!(font.ascent, atlas.fonts[0].ascent);
+    let a = font.glyphs.iter().find(|g| g.ch == 'a').unwrap();
+    let rect = atlas.fonts[0].glyphs[&'a'].rect;
+    assert_eq!((a.width, a.height), (rect.w, rect.h));
+    assert!(a.alpha.iter().any(|&v| v != 0));
+}
@@ -0,0 +1,35 @@
+use microui::batch::Batch;
+use microui::*;
+
+struct Kerned;
+
+impl Font for Kerned {
+    fn char_width(&self, font: FontId, c: char) -> i32 { AtlasFont.char_width(font, c) }
+
+    fn font_height(&self, font: FontId) -> i32 { AtlasFont.font_height(font) }
+
+    fn kerning(&self, _font: FontId, left: char, right: char) -> i32 {
+        match (left, right) {
+            ('A', 'V') | ('V', 'A') => -3,
+            _ => 0,
+        }
+    }
+}
+
+#[test]
+fn drawn_text_is_kerned_like_measured_text() {
+    let mut ctx = Context::new(Kerned);
+    let opt = WidgetOption::NO_FRAME | WidgetOption::NO_TITLE | WidgetOption::NO_RESIZE;
+    ctx.frame(|ui| {
+        ui.window("W").position(0, 0).size(200, 50).options(opt).show(ui, |ui| ui.draw_text(FontId(0), "AVA", vec2(0, 0), Color::rgba(0, 0, 0, 255)));
+    });
+
+    let mut batch = Batch::new();
+    batch.set_font(&Kerned);
+    batch.build(&ctx);
+    // one quad per glyph, the right edge of the last one ends where the measured text does
+    let right = batch.vertices.iter().map(|v| v.pos[0]).fold(0.0, f32::max);
+    assert_eq!(batch.vertices.len(), 12);
+    assert_eq!(right as i32, ctx.get_text_width(FontId(0), "AVA"));
+    assert_eq!(batch.vertices[4].pos[0] as i32, AtlasFont.char_width(FontId(0), 'A') - 3);
+}
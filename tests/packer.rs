@@ -0,0 +1,68 @@
+use microui::atlas::*;
+use microui::*;
+
+fn overlaps(a: Rect, b: Rect) -> bool { a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h }
+
+fn assert_disjoint(rects: &[Rect]) {
+    for (i, &a) in rects.iter().enumerate() {
+        for &b in &rects[i + 1..] {
+            assert!(!overlaps(a, b), "{},{} {}x{} overlaps {},{} {}x{}", a.x, a.y, a.w, a.h, b.x, b.y, b.w, b.h);
+        }
+    }
+}
+
+#[test]
+fn placements_do_not_overlap() {
+    let mut packer = Packer::new(64, 64);
+    let mut rects = Vec::new();
+    for i in 0..40 {
+        let (w, h) = (3 + i * 7 % 13, 2 + i * 5 % 11);
+        if let Some(r) = packer.pack(w, h) {
+            assert!(r.x >= 0 && r.y >= 0 && r.x + r.w <= 64 && r.y + r.h <= 64);
+            assert_eq!((r.w, r.h), (w, h));
+            rects.push(r);
+        }
+    }
+    assert!(rects.len() > 20);
+    assert_disjoint(&rects);
+}
+
+#[test]
+fn with_used_avoids_builtin_glyphs() {
+    let used: Vec<Rect> = ATLAS.iter().chain(ATLAS_ICONS.iter()).copied().filter(|r| r.w > 0 && r.h > 0).collect();
+    let mut packer = Packer::with_used(ATLAS_WIDTH as i32, ATLAS_HEIGHT as i32, used.iter().copied());
+    let mut packed = Vec::new();
+    while let Some(r) = packer.pack(4, 4) {
+        packed.push(r);
+    }
+    assert!(!packed.is_empty());
+    assert_disjoint(&packed);
+    for &r in &packed {
+        assert!(used.iter().all(|&u| !overlaps(r, u)));
+    }
+}
+
+#[test]
+fn grow_makes_room_below() {
+    let mut packer = Packer::new(16, 8);
+    assert!(packer.pack(16, 8).is_some());
+    assert!(packer.pack(4, 4).is_none());
+    packer.grow(16);
+    assert_eq!(packer.height(), 16);
+    let r = packer.pack(4, 4).unwrap();
+    assert_eq!((r.x, r.y), (0, 8));
+    // growing never shrinks
+    packer.grow(4);
+    assert_eq!(packer.height(), 16);
+}
+
+#[test]
+fn narrowest_atlas_holds_white_patch() {
+    let atlas = Atlas::new(4, 0);
+    assert_eq!((atlas.white.w, atlas.white.h), (3, 3));
+    assert!(atlas.height >= 4);
+}
+
+#[test]
+#[should_panic(expected = "at least 4 pixels wide")]
+fn atlas_narrower_than_white_patch_panics() { Atlas::new(2, 2); }
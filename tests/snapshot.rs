@@ -0,0 +1,122 @@
+use microui::batch::*;
+use microui::snapshot::*;
+use microui::*;
+
+fn demo_window(ui: &mut Context, checks: &mut [bool; 2], value: &mut f32, text: &mut String) {
+    ui.window("Demo Window").position(8, 8).size(240, 200).options(WidgetOption::SHADOW).show(ui, |ui| {
+        ui.header("Test Buttons").expanded().show(ui, |ui| {
+            ui.layout_row(&[86, -1], 0);
+            ui.label("Test buttons:");
+            ui.button("Button 1").show(ui, |_| ());
+            ui.checkbox("Checkbox 1", &mut checks[0]);
+            ui.checkbox("Checkbox 2", &mut checks[1]);
+        });
+        ui.layout_row(&[-1], 0);
+        ui.slider_ex(value, 0.0, 100.0, 0.0, 2, WidgetOption::ALIGN_CENTER);
+        ui.textbox_ex(text, WidgetOption::empty());
+    });
+}
+
+#[test]
+fn demo_window_matches_golden() {
+    let mut ctx = Context::new(AtlasFont);
+    let (mut checks, mut value, mut text) = ([false, true], 40.0, String::from("hello"));
+    let snapshot = Snapshot::capture(&mut ctx, 256, 224, Color::rgba(90, 95, 100, 255), |ui| demo_window(ui, &mut checks, &mut value, &mut text));
+    assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/demo_window.ppm"), &snapshot, 0);
+}
+
+fn styled_scene(ui: &mut Context, value: &mut f32) {
+    ui.window("Styled").position(8, 8).size(200, 120).show(ui, |ui| {
+        ui.layout_row(&[90, -1], 0);
+        ui.button("Open").show(ui, |ui| ui.open_popup("Menu"));
+        ui.button("Rounded").show(ui, |_| ());
+        ui.layout_row(&[-1], 0);
+        ui.slider_ex(value, 0.0, 10.0, 0.0, 1, WidgetOption::ALIGN_CENTER);
+    });
+    ui.popup("Menu").show(ui, |ui| {
+        ui.label("Popup item");
+        ui.button("Choose").show(ui, |_| ());
+    });
+}
+
+/// A context showing `styled_scene` with rounded corners, gradients and the popup open with a soft shadow.
+fn styled_context(value: &mut f32) -> Context {
+    let mut ctx = Context::new(AtlasFont);
+    ctx.style.corner_radius = 5;
+    ctx.style.border_width = 1;
+    ctx.style.shadow_offset = vec2(4, 4);
+    ctx.style.shadow_blur = 6;
+    ctx.style.gradients[ControlColor::TitleBG as usize] = Some(Color::rgba(10, 40, 90, 255));
+    ctx.style.gradients[ControlColor::Button as usize] = Some(Color::rgba(30, 30, 30, 255));
+    ctx.input_mousemove(60, 90);
+    ctx.frame(|ui| {
+        styled_scene(ui, value);
+        ui.open_popup("Menu");
+    });
+    ctx.input_mousemove(0, 0);
+    // the popup sizes itself to its contents over the first frames
+    for _ in 0..2 {
+        ctx.frame(|ui| styled_scene(ui, value));
+    }
+    ctx
+}
+
+#[test]
+fn styled_scene_matches_golden() {
+    let mut value = 3.0;
+    let mut ctx = styled_context(&mut value);
+    let snapshot = Snapshot::capture(&mut ctx, 240, 180, Color::rgba(90, 95, 100, 255), |ui| styled_scene(ui, &mut value));
+    assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/styled_scene.ppm"), &snapshot, 0);
+}
+
+#[test]
+fn styled_scene_batches_gradients_shadows_and_corners() {
+    let mut value = 3.0;
+    let mut ctx = styled_context(&mut value);
+    ctx.frame(|ui| styled_scene(ui, &mut value));
+    let mut batch = Batch::new();
+    batch.build(&ctx);
+    let rgba = |c: Color| [c.r, c.g, c.b, c.a];
+    let has_color = |c: Color| batch.vertices.iter().any(|v| rgba(v.color) == rgba(c));
+    assert!(has_color(Color::rgba(10, 40, 90, 255)) && has_color(ctx.style.colors[ControlColor::TitleBG as usize]));
+    assert!(has_color(Color::rgba(30, 30, 30, 255)));
+
+    // the shadow fades out as blur + 1 stacked translucent layers
+    let shadow = ctx.style.colors[ControlColor::Shadow as usize];
+    let layers: Vec<&Vertex> = batch.vertices.iter().filter(|v| rgba(v.color)[..3] == rgba(shadow)[..3]).collect();
+    let blur = ctx.style.shadow_blur as usize;
+    assert!(layers.len() >= (blur + 1) * 4 && layers.iter().all(|v| v.color.a > 0 && v.color.a < shadow.a));
+
+    // the window body's bottom left corner is cut along an arc rather than ending in a square corner
+    let body = rgba(ctx.style.colors[ControlColor::WindowBG as usize]);
+    let on_arc = |v: &Vertex| rgba(v.color) == body && v.pos[0] > 8.0 && v.pos[0] < 13.0 && v.pos[1] > 123.0 && v.pos[1] < 128.0;
+    assert!(batch.vertices.iter().any(on_arc));
+}
+
+#[test]
+fn ppm_round_trip() {
+    let mut image = Snapshot::new(3, 2);
+    for (i, px) in image.pixels.chunks_exact_mut(4).enumerate() {
+        px.copy_from_slice(&[i as u8 * 40, 255 - i as u8, i as u8, 255]);
+    }
+    let decoded = Snapshot::from_ppm(&image.to_ppm()).unwrap();
+    assert_eq!((decoded.width, decoded.height), (3, 2));
+    assert_eq!(decoded.pixels, image.pixels);
+    assert!(decoded.diff(&image, 0).is_none());
+
+    let commented = b"P6\n# comment\n1 1\n255\n\x01\x02\x03";
+    assert_eq!(Snapshot::from_ppm(commented).unwrap().pixels, [1, 2, 3, 255]);
+    assert!(Snapshot::from_ppm(b"P6\n2 2\n255\n\x00").is_none());
+    assert!(Snapshot::from_ppm(b"P3\n1 1\n255\n0 0 0").is_none());
+    assert!(Snapshot::from_ppm(b"P6\n18446744073709551615 18446744073709551615\n255\n\x00").is_none());
+}
+
+#[test]
+#[should_panic(expected = "is missing")]
+fn missing_reference_fails() {
+    let dir = std::env::temp_dir().join(format!("microui-missing-{}", std::process::id()));
+    if std::env::var_os("MICROUI_BLESS").is_none() {
+        assert_snapshot(dir.join("none.ppm"), &Snapshot::new(1, 1), 0);
+    }
+    panic!("is missing");
+}
@@ -0,0 +1,72 @@
+use microui::*;
+
+fn textbox(ctx: &mut Context, buf: &mut String) -> ResourceState {
+    let mut res = ResourceState::empty();
+    ctx.frame(|ui| {
+        ui.window("T").position(0, 0).size(200, 100).show(ui, |ui| res = ui.textbox_ex(buf, WidgetOption::empty()));
+    });
+    res
+}
+
+/// Gives the textbox keyboard focus with Tab. Its id comes from the address of `buf`, which must not move afterwards.
+fn focus(ctx: &mut Context, buf: &mut String) {
+    textbox(ctx, buf);
+    ctx.input_keydown(Key::Tab);
+    textbox(ctx, buf);
+    assert_eq!(ctx.text_cursor, buf.len());
+}
+
+fn press(ctx: &mut Context, buf: &mut String, key: Key) -> ResourceState {
+    ctx.input_keydown(key);
+    ctx.input_keyup(key);
+    textbox(ctx, buf)
+}
+
+#[test]
+fn caret_moves_and_edits_over_multibyte_text() {
+    let (mut ctx, mut buf) = (Context::new(AtlasFont), String::from("aé€b"));
+    focus(&mut ctx, &mut buf);
+    let steps = [
+        (Key::Left, "aé€b", 6, false),
+        (Key::Left, "aé€b", 3, false),
+        (Key::Backspace, "a€b", 1, true),
+        (Key::Delete, "ab", 1, true),
+        (Key::Home, "ab", 0, false),
+        (Key::Left, "ab", 0, false),
+        (Key::Backspace, "ab", 0, false),
+        (Key::End, "ab", 2, false),
+        (Key::Right, "ab", 2, false),
+        (Key::Delete, "ab", 2, false),
+        (Key::Backspace, "a", 1, true),
+    ];
+    for (key, text, cursor, changed) in steps {
+        let res = press(&mut ctx, &mut buf, key);
+        assert_eq!((buf.as_str(), ctx.text_cursor, res.is_changed()), (text, cursor, changed), "after {:?}", key);
+    }
+}
+
+#[test]
+fn text_is_inserted_at_caret() {
+    let (mut ctx, mut buf) = (Context::new(AtlasFont), String::from("ac"));
+    focus(&mut ctx, &mut buf);
+    press(&mut ctx, &mut buf, Key::Left);
+    ctx.input_text("b");
+    ctx.input_text("€");
+    assert!(textbox(&mut ctx, &mut buf).is_changed());
+    assert_eq!((buf.as_str(), ctx.text_cursor), ("ab€c", 5));
+    press(&mut ctx, &mut buf, Key::Home);
+    ctx.input_text("é");
+    textbox(&mut ctx, &mut buf);
+    assert_eq!((buf.as_str(), ctx.text_cursor), ("éab€c", 2));
+}
+
+#[test]
+fn return_submits_and_releases_focus() {
+    let (mut ctx, mut buf) = (Context::new(AtlasFont), String::from("x"));
+    focus(&mut ctx, &mut buf);
+    assert!(press(&mut ctx, &mut buf, Key::Return).is_submitted());
+    assert!(ctx.focus.is_none());
+    ctx.input_text("y");
+    textbox(&mut ctx, &mut buf);
+    assert_eq!(buf, "x");
+}
@@ -0,0 +1,102 @@
+use microui::wire::*;
+use microui::*;
+
+fn sample() -> Frame {
+    let (clip, c) = (rect(1, 2, 300, 400), Color::rgba(10, 20, 30, 40));
+    let commands = vec![
+        Command::Rect { rect: rect(1, 2, 3, 4), color: c, clip },
+        Command::Text { font: FontId(2), pos: vec2(-5, 6), color: c, str_start: 0, str_len: 6, clip: Rect::UNCLIPPED },
+        Command::Icon { rect: rect(5, 6, 7, 8), id: Icon::User(3), color: c, clip },
+        Command::Image { texture: TextureId(9), rect: rect(0, 0, 16, 16), uv: uv_rect(0.0, 0.25, 0.5, 1.0), color: c, clip },
+        Command::Line { p0: vec2(0, 0), p1: vec2(10, -10), thickness: 2, color: c, clip },
+        Command::Polyline { points_start: 0, points_len: 3, thickness: 3, color: c, clip },
+        Command::Triangle { points: [vec2(0, 0), vec2(4, 0), vec2(0, 4)], color: c, clip },
+        Command::RoundedRect { rect: rect(2, 2, 20, 20), radius: 4, border: 1, color: c, clip },
+        Command::Gradient { rect: rect(3, 3, 30, 30), radius: 2, colors: [c, Color::rgba(1, 2, 3, 4), c, Color::rgba(5, 6, 7, 8)], clip },
+        Command::Custom { id: 77, rect: rect(4, 4, 40, 40), clip },
+    ];
+    Frame { commands, text: String::from("héllo"), points: vec![vec2(1, 1), vec2(2, 3), vec2(5, 8)], scale: 1.5 }
+}
+
+fn encode(frame: &Frame) -> Vec<u8> {
+    let mut res = Vec::new();
+    frame.write(&mut res).unwrap();
+    res
+}
+
+#[test]
+fn round_trip_every_tag() {
+    let frame = sample();
+    let bytes = encode(&frame);
+    let read = Frame::read(&mut &bytes[..]).unwrap().unwrap();
+    assert_eq!(read.commands.len(), frame.commands.len());
+    assert_eq!(read.scale, frame.scale);
+    assert_eq!(read.dump(), frame.dump());
+    assert_eq!(encode(&read), bytes);
+}
+
+#[test]
+fn recording_round_trip() {
+    let mut rec = Recorder::new(Vec::new()).unwrap();
+    rec.write_frame(&sample()).unwrap();
+    rec.write_frame(&Frame::default()).unwrap();
+    let data = rec.into_inner();
+    let frames = Replay::new(&data[..]).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].dump(), sample().dump());
+    assert!(frames[1].commands.is_empty());
+
+    assert!(matches!(Replay::new(&b"NOPE\x04\x00"[..]), Err(WireError::BadMagic)));
+    assert!(matches!(Replay::new(&b"MUI\0\x63\x00"[..]), Err(WireError::UnsupportedVersion(99))));
+    assert_eq!(VERSION, 1);
+    assert_eq!(&data[..6], b"MUI\0\x01\x00");
+}
+
+#[test]
+fn corrupt_lengths_are_errors() {
+    // one text command claiming a 4 GiB string, then the stream ends
+    let mut text = vec![1, 0, 0, 0, 0, 0, 0x80, 0x3f, 2];
+    text.extend_from_slice(&[0; 16 + 4 + 8 + 4]);
+    text.extend_from_slice(&u32::MAX.to_le_bytes());
+    text.extend_from_slice(b"abc");
+    assert!(matches!(Frame::read(&mut &text[..]), Err(WireError::Io(_))));
+
+    // one polyline claiming u32::MAX points
+    let mut polyline = vec![1, 0, 0, 0, 0, 0, 0x80, 0x3f, 6];
+    polyline.extend_from_slice(&[0; 16]);
+    polyline.extend_from_slice(&u32::MAX.to_le_bytes());
+    polyline.extend_from_slice(&[0; 8]);
+    assert!(matches!(Frame::read(&mut &polyline[..]), Err(WireError::Io(_))));
+
+    let mut bad_tag = vec![1, 0, 0, 0, 0, 0, 0x80, 0x3f, 200];
+    bad_tag.extend_from_slice(&[0; 16]);
+    assert!(matches!(Frame::read(&mut &bad_tag[..]), Err(WireError::InvalidTag(200))));
+    assert!(Frame::read(&mut &[][..]).unwrap().is_none());
+}
+
+#[test]
+fn user_icons_round_trip() {
+    let mut atlas = microui::atlas::Atlas::new(64, 64);
+    let icons: Vec<Icon> = (0..3).map(|_| atlas.add_icon(2, 2, microui::atlas::Pixels::Alpha(&[255; 4]))).collect();
+    assert!(icons == [Icon::User(0), Icon::User(1), Icon::User(2)]);
+    for &icon in Icon::BUILTIN.iter().chain(&icons) {
+        assert!(Icon::from_index(icon.index()) == Some(icon));
+    }
+    assert!(icons.iter().all(|&icon| atlas.icon(icon).w == 2));
+    assert_eq!(atlas.icons.len(), icons[2].index() + 1);
+
+    let mut ctx = Context::new(AtlasFont);
+    let opt = WidgetOption::NO_FRAME | WidgetOption::NO_TITLE | WidgetOption::NO_RESIZE;
+    ctx.frame(|ui| {
+        ui.window("W").position(0, 0).size(50, 50).options(opt).show(ui, |ui| {
+            ui.draw_icon(icons[1], rect(4, 4, 8, 8), Color::rgba(1, 2, 3, 255));
+            ui.draw_icon(Icon::Warning, rect(12, 4, 8, 8), Color::rgba(1, 2, 3, 255));
+        });
+    });
+    let expected = "icon user1 4,4 8x8 #010203ff\nicon warning 12,4 8x8 #010203ff\n";
+    assert_eq!(ctx.dump_commands(), expected);
+    let bytes = encode(&Frame::capture(&ctx));
+    let read = Frame::read(&mut &bytes[..]).unwrap().unwrap();
+    assert!(matches!(read.commands[0], Command::Icon { id: Icon::User(1), .. }));
+    assert_eq!(read.dump(), expected);
+}